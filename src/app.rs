@@ -1,18 +1,97 @@
-use std::{error::Error, process::Command};
-use tui::widgets::ListState;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    sync::Arc,
+    time::Instant,
+};
 
-#[derive(PartialEq)]
+use tui::widgets::{ListState, TableState};
+
+use crate::bulk::{self, BulkSummary, ItemResults};
+use crate::clipboard;
+use crate::debug::{self, CopyPodForm};
+use crate::delete::{self, PendingDelete};
+use crate::describe::DescribeView;
+use crate::edit::{self, PendingEdit};
+use crate::events::{self, Event, InvolvedObject};
+use crate::filter::{self, ListFilter};
+use crate::kube::KubeBackend;
+use crate::logs::LogView;
+use crate::manifest::ManifestView;
+use crate::metrics::{Metrics, Usage};
+use crate::nodes::{Drain, Node, PendingDrain};
+use crate::pods::{self, Container, Pod, PodSortColumn};
+use crate::portforward::{self, PortForward};
+use crate::resources::ApiResource;
+use crate::secrets::{Secret, SecretView};
+use crate::watch::{ListWatch, RowChange};
+use crate::worker::Worker;
+use crate::workloads::{self, Deployment, Revision, RolloutWatch, Workload, WorkloadKind};
+
+#[derive(Clone, Copy, PartialEq)]
 pub enum AppState {
     MainMenu,
     NamespaceSelection,
     ContextSelection,
     ExecPodSelection,
     PodSelection,
-    CopyPodNameInput,
+    CopyPodForm,
+    LogContainerSelection,
+    PodLogs,
+    ExecContainerSelection,
+    ShellSelection,
+    ExecCommandInput,
+    DebugPodSelection,
+    DebugContainerSelection,
+    DebugImageSelection,
+    Describe,
+    Manifest,
+    EditConfirm,
+    /// Waiting for `kubectl apply` of a confirmed edit.
+    Applying,
+    ResourceKindSelection,
+    ResourceList,
+    ConfirmDelete,
+    DeploymentList,
+    ScaleInput,
+    RevisionSelection,
+    RolloutStatus,
+    StatefulSetList,
+    DaemonSetList,
+    JobList,
+    CronJobList,
+    PortForwardInput,
+    PortForwardList,
+    BulkSummary,
+    EventList,
+    NodeList,
+    NodePodList,
+    DrainOptions,
+    DrainProgress,
+    SecretList,
+    SecretDetail,
     Message,
     ShowOutput,
 }
 
+impl AppState {
+    pub fn workload_list(kind: WorkloadKind) -> AppState {
+        match kind {
+            WorkloadKind::StatefulSet => AppState::StatefulSetList,
+            WorkloadKind::DaemonSet => AppState::DaemonSetList,
+            WorkloadKind::Job => AppState::JobList,
+            WorkloadKind::CronJob => AppState::CronJobList,
+        }
+    }
+
+    /// The kind listed on this screen, if it is one of the workload lists.
+    pub fn workload_kind(self) -> Option<WorkloadKind> {
+        WorkloadKind::ALL
+            .into_iter()
+            .find(|&kind| AppState::workload_list(kind) == self)
+    }
+}
+
 pub struct App {
     pub state: AppState,
 
@@ -25,27 +104,220 @@ pub struct App {
     pub contexts: Vec<String>,
     pub context_list_state: ListState,
 
-    pub pods: Vec<String>,
-    pub pod_list_state: ListState,
+    pub pods: Vec<Pod>,
+    pub pod_list_state: TableState,
+    pub pod_sort: PodSortColumn,
+    pub pod_sort_descending: bool,
+
+    pub containers: Vec<Container>,
+    pub container_list_state: ListState,
+    pub logs: Option<LogView>,
+
+    pub selected_container: Option<String>,
+    pub shell_list_state: ListState,
+    pub exec_command: String,
 
     pub selected_namespace: Option<String>,
     pub selected_context: Option<String>,
     pub selected_pod: Option<String>,
+    /// The pod list the log and exec screens go back to: the pods of the
+    /// namespace or of a node.
+    pub pod_return: AppState,
 
     pub default_namespace: String,
 
-    pub input: String,
-    pub new_pod_name: String,
+    pub copy_form: CopyPodForm,
+
+    pub debug_images: Vec<String>,
+    pub debug_image_list_state: ListState,
+
+    pub describe: Option<DescribeView>,
+    pub manifest: Option<ManifestView>,
+    pub api_resources: Vec<ApiResource>,
+    pub api_resource_list_state: ListState,
+    pub selected_kind: Option<ApiResource>,
+    pub resources: Vec<String>,
+    pub resource_list_state: ListState,
+    pub pending_delete: Option<PendingDelete>,
+
+    pub port_forwards: Vec<PortForward>,
+    pub port_forward_list_state: TableState,
+    pub port_forward_input: String,
+    pub pending_forward: Option<PendingForward>,
+
+    /// Names marked for a bulk action in the pod, resource, deployment or
+    /// workload lists. The copy and debug pickers and the secret list choose
+    /// a single item to open, so they have no marks.
+    pub marked: BTreeSet<String>,
+    pub bulk: Option<BulkSummary>,
+    /// The `/` filter narrowing the namespace, context, pod or resource list.
+    pub filter: ListFilter,
+    pub watch: ListWatch,
+    /// `kubectl top` usage of pods and nodes, with recent history.
+    pub metrics: Metrics,
+
+    pub deployments: Vec<Deployment>,
+    pub deployment_list_state: TableState,
+    pub scale_input: String,
+    pub revisions: Vec<Revision>,
+    pub revision_list_state: ListState,
+    pub rollout: Option<RolloutWatch>,
+
+    pub workloads: Vec<Workload>,
+    pub workload_list_state: TableState,
+
+    pub events: Vec<Event>,
+    pub event_list_state: TableState,
+    /// Lists events from every namespace rather than the current one.
+    pub events_all_namespaces: bool,
+    /// `events_all_namespaces` to restore on leaving events it was set for.
+    saved_events_all_namespaces: Option<bool>,
+    pub event_object: Option<InvolvedObject>,
+    pub events_return: AppState,
+
+    pub nodes: Vec<Node>,
+    pub node_list_state: TableState,
+    /// The node whose pods `NodePodList` shows.
+    pub selected_node: Option<String>,
+    pub pending_drain: Option<PendingDrain>,
+    pub drain: Option<Drain>,
+
+    pub secrets: Vec<Secret>,
+    pub secret_list_state: TableState,
+    pub secret_view: Option<SecretView>,
+    /// Command that copies its stdin, or None to copy through the terminal.
+    pub clipboard: Option<String>,
+
+    pub editor: String,
+    pub pending_edit: Option<PendingEdit>,
+    /// The list screen a describe or manifest view was opened from.
+    pub detail_return: AppState,
 
     pub message: String,
     pub output: String,
+    pub status_message: Option<String>,
+
+    pub interactive: Option<Interactive>,
 
     pub last_main_menu_index: Option<usize>,
+
+    pub worker: Worker<JobResult>,
+    pub tick: usize,
+    pub kube: Arc<dyn KubeBackend>,
+}
+
+/// What a container lookup was started for.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ContainerAction {
+    Logs,
+    Exec,
+    Copy,
+    Debug,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Sh,
+    Ash,
+    Custom,
+}
+
+impl Shell {
+    pub const ALL: [Shell; 4] = [Shell::Bash, Shell::Sh, Shell::Ash, Shell::Custom];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash (falls back to sh)",
+            Shell::Sh => "sh",
+            Shell::Ash => "ash",
+            Shell::Custom => "Custom command...",
+        }
+    }
+
+    fn command(self) -> Option<&'static str> {
+        match self {
+            Shell::Bash => Some("bash"),
+            Shell::Sh => Some("sh"),
+            Shell::Ash => Some("ash"),
+            Shell::Custom => None,
+        }
+    }
+}
+
+/// A session that needs the real terminal. `run_app` suspends the TUI while
+/// it runs and restores it afterwards.
+pub enum Interactive {
+    Exec {
+        pod: String,
+        container: Option<String>,
+        command: Vec<String>,
+    },
+    /// Opens a manifest in the user's editor.
+    Edit {
+        kind: String,
+        name: String,
+        yaml: String,
+    },
+    /// A `kubectl debug` invocation that attaches to the debug container.
+    Debug {
+        pod: String,
+        args: Vec<String>,
+        return_to: AppState,
+    },
+}
+
+/// A port forward waiting for its ports to be entered.
+pub struct PendingForward {
+    pub namespace: String,
+    pub target: String,
+    pub return_to: AppState,
+}
+
+/// Results posted back by background kubectl jobs.
+pub enum JobResult {
+    Namespaces(AppState, Result<Vec<String>, String>),
+    Contexts(Result<Vec<String>, String>),
+    Pods(AppState, Result<Vec<Pod>, String>),
+    Containers(AppState, ContainerAction, String, Result<Vec<Container>, String>),
+    ApiResources(Result<Vec<ApiResource>, String>),
+    Resources(AppState, Result<Vec<String>, String>),
+    /// Outcome of a one-off kubectl action, with the label it ran under.
+    Action(String, Result<String, String>),
+    Deployments(AppState, Result<Vec<Deployment>, String>),
+    Workloads(AppState, WorkloadKind, Result<Vec<Workload>, String>),
+    /// Per-item outcomes of a bulk action, with its label.
+    Bulk(AppState, String, ItemResults),
+    /// Recent logs of each marked pod.
+    Logs(AppState, Vec<String>, ItemResults),
+    Events(AppState, Result<Vec<Event>, String>),
+    Nodes(AppState, Result<Vec<Node>, String>),
+    Secrets(AppState, Result<Vec<Secret>, String>),
+    /// Outcome of copying the value of a secret key to the clipboard.
+    Copied(String, Result<(), String>),
+    /// Pod usage in the namespace, or in all namespaces if None.
+    PodMetrics(Option<String>, Result<HashMap<String, Usage>, String>),
+    NodeMetrics(Result<HashMap<String, Usage>, String>),
+    /// Pods scheduled on the named node.
+    NodePods(AppState, String, Result<Vec<Pod>, String>),
+    RolloutHistory(Result<String, String>),
+    /// `rollout status` output and the refreshed deployment list.
+    RolloutStatus(Result<String, String>, Result<Vec<Deployment>, String>),
+    /// `kubectl describe` output for (kind, name), requested from a list screen.
+    Describe(AppState, String, String, Result<String, String>),
+    /// `kubectl get -o yaml` output for (kind, name).
+    Manifest(AppState, String, String, Result<String, String>),
+    /// `kubectl diff` of an edit against the live object.
+    EditDiff(AppState, PendingEdit, Result<String, String>),
+    Output(String),
+    /// Output of a one-off command, shown full screen if the screen that
+    /// started it is still open.
+    Finished(AppState, Result<String, String>),
 }
 
 impl App {
-    pub fn new() -> Self {
-        let default_context = Self::get_current_context();
+    pub fn new(kube: Arc<dyn KubeBackend>) -> App {
+        let default_context = kube.current_context();
 
         let mut list_state = ListState::default();
         list_state.select(Some(0));
@@ -58,6 +330,17 @@ impl App {
                 "Choose Namespace".to_string(),
                 "Pods".to_string(),
                 "Copy Pod".to_string(),
+                "Debug Pod".to_string(),
+                "Resources".to_string(),
+                "Deployments".to_string(),
+                "StatefulSets".to_string(),
+                "DaemonSets".to_string(),
+                "Jobs".to_string(),
+                "CronJobs".to_string(),
+                "Port Forwards".to_string(),
+                "Events".to_string(),
+                "Nodes".to_string(),
+                "Secrets".to_string(),
             ],
             list_state,
 
@@ -68,38 +351,92 @@ impl App {
             context_list_state: ListState::default(),
 
             pods: Vec::new(),
-            pod_list_state: ListState::default(),
+            pod_list_state: TableState::default(),
+            pod_sort: PodSortColumn::Name,
+            pod_sort_descending: false,
+
+            containers: Vec::new(),
+            container_list_state: ListState::default(),
+            logs: None,
+
+            selected_container: None,
+            shell_list_state: ListState::default(),
+            exec_command: String::new(),
 
             selected_namespace: None,
             selected_context: default_context,
             selected_pod: None,
+            pod_return: AppState::ExecPodSelection,
 
             default_namespace: "default".to_string(),
 
-            input: String::new(),
-            new_pod_name: String::new(),
+            copy_form: CopyPodForm::default(),
+
+            debug_images: debug::debug_images(),
+            debug_image_list_state: ListState::default(),
+
+            describe: None,
+            manifest: None,
+            api_resources: Vec::new(),
+            api_resource_list_state: ListState::default(),
+            selected_kind: None,
+            resources: Vec::new(),
+            resource_list_state: ListState::default(),
+            pending_delete: None,
+
+            port_forwards: Vec::new(),
+            port_forward_list_state: TableState::default(),
+            port_forward_input: String::new(),
+            pending_forward: None,
+
+            marked: BTreeSet::new(),
+            bulk: None,
+            filter: ListFilter::default(),
+            watch: ListWatch::default(),
+            metrics: Metrics::default(),
+
+            deployments: Vec::new(),
+            deployment_list_state: TableState::default(),
+            scale_input: String::new(),
+            revisions: Vec::new(),
+            revision_list_state: ListState::default(),
+            rollout: None,
+
+            workloads: Vec::new(),
+            workload_list_state: TableState::default(),
+
+            events: Vec::new(),
+            event_list_state: TableState::default(),
+            events_all_namespaces: false,
+            saved_events_all_namespaces: None,
+            event_object: None,
+            events_return: AppState::MainMenu,
+
+            nodes: Vec::new(),
+            node_list_state: TableState::default(),
+            selected_node: None,
+            pending_drain: None,
+            drain: None,
+
+            secrets: Vec::new(),
+            secret_list_state: TableState::default(),
+            secret_view: None,
+            clipboard: clipboard::clipboard_command(),
+
+            editor: edit::editor_command(),
+            pending_edit: None,
+            detail_return: AppState::MainMenu,
             message: String::new(),
             output: String::new(),
+            status_message: None,
 
-            last_main_menu_index: None,
-        }
-    }
+            interactive: None,
 
-    fn get_current_context() -> Option<String> {
-        let output = Command::new("kubectl")
-            .args(&["config", "current-context"])
-            .output()
-            .ok()?;
+            last_main_menu_index: None,
 
-        if output.status.success() {
-            let ctx = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !ctx.is_empty() {
-                Some(ctx)
-            } else {
-                None
-            }
-        } else {
-            None
+            worker: Worker::new(),
+            tick: 0,
+            kube,
         }
     }
 
@@ -109,130 +446,1899 @@ impl App {
             .unwrap_or_else(|| self.default_namespace.clone())
     }
 
-    pub fn load_namespaces(&mut self) -> Result<(), Box<dyn Error>> {
-        let output = Command::new("kubectl")
-            .args(&["get", "namespaces", "-o=jsonpath='{.items[*].metadata.name}'"])
-            .output()?;
+    pub fn load_namespaces(&mut self) {
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let task = move || JobResult::Namespaces(origin, kube.list_namespaces());
+        if origin == AppState::NamespaceSelection {
+            self.worker.spawn_background("Refreshing namespaces", task);
+        } else {
+            self.worker.spawn("Loading namespaces", task);
+        }
+    }
 
-        if output.status.success() {
-            let ns_output = String::from_utf8_lossy(&output.stdout)
-                .trim_matches('\'')
-                .to_string();
-            self.namespaces = ns_output
-                .split_whitespace()
-                .map(|s| s.to_string())
-                .collect();
-            self.namespace_list_state.select(Some(0));
-            Ok(())
+    pub fn load_contexts(&mut self) {
+        let kube = self.kube.clone();
+        self.worker.spawn("Loading contexts", move || {
+            JobResult::Contexts(kube.list_contexts())
+        });
+    }
+
+    pub fn load_pods(&mut self, next: AppState) {
+        let kube = self.kube.clone();
+        let namespace = self.current_namespace();
+        let task = move || JobResult::Pods(next, kube.list_pods(&namespace));
+        if self.state == next {
+            self.worker.spawn_background("Refreshing pods", task);
         } else {
-            let error_msg = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Failed to get namespaces: {}", error_msg).into())
+            self.worker.spawn("Loading pods", task);
         }
     }
 
-    pub fn load_contexts(&mut self) -> Result<(), Box<dyn Error>> {
-        let output = Command::new("kubectl")
-            .args(&["config", "get-contexts", "-o=name"])
-            .output()?;
+    /// Reloads the pod or namespace list on screen once the watch interval
+    /// has passed since it was last loaded.
+    pub fn poll_watch(&mut self) {
+        let watched = matches!(
+            self.state,
+            AppState::NamespaceSelection
+                | AppState::ExecPodSelection
+                | AppState::PodSelection
+                | AppState::DebugPodSelection
+        );
+        if !watched || self.worker.is_busy() || !self.watch.due(Instant::now()) {
+            return;
+        }
+        // Waiting for this load keeps a slow cluster from being polled again.
+        self.watch.last_refresh = Some(Instant::now());
+        match self.state {
+            AppState::NamespaceSelection => self.load_namespaces(),
+            state => self.load_pods(state),
+        }
+    }
 
-        if output.status.success() {
-            self.contexts = String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .map(|s| s.to_string())
-                .collect();
-            self.context_list_state.select(Some(0));
-            Ok(())
+    /// How `name` changed in the latest refresh of the watched list on screen.
+    pub fn row_change(&self, name: &str) -> Option<RowChange> {
+        match self.list_screen() {
+            AppState::NamespaceSelection => self.watch.changes.get(name).copied(),
+            _ if self.is_pod_list() => self.watch.changes.get(name).copied(),
+            _ => None,
+        }
+    }
+
+    pub fn selected_pod_name(&self) -> Option<String> {
+        let selected = self.unfiltered_index(self.pod_list_state.selected())?;
+        self.pods.get(selected).map(|p| p.name.clone())
+    }
+
+    /// Re-sorts the pod table, keeping the highlighted pod selected.
+    pub fn sort_pods(&mut self, column: PodSortColumn, descending: bool) {
+        let selected = self.selected_pod_name();
+        self.pod_sort = column;
+        self.pod_sort_descending = descending;
+        pods::sort_pods(&mut self.pods, column, descending);
+        self.select_name(selected);
+    }
+
+    pub fn load_containers(&mut self, pod: &str, action: ContainerAction) {
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let namespace = self.pod_namespace(pod);
+        let pod = pod.to_string();
+        self.worker.spawn("Loading containers", move || {
+            let result = kube.list_containers(&namespace, &pod);
+            JobResult::Containers(origin, action, pod, result)
+        });
+    }
+
+    pub fn load_api_resources(&mut self) {
+        let kube = self.kube.clone();
+        self.worker.spawn("Loading API resources", move || {
+            JobResult::ApiResources(kube.list_api_resources())
+        });
+    }
+
+    /// Lists instances of `selected_kind`, in the current namespace for
+    /// namespaced kinds.
+    pub fn load_resources(&mut self) {
+        let Some(kind) = self.selected_kind.clone() else {
+            return;
+        };
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let namespace = kind.namespaced.then(|| self.current_namespace());
+        self.worker.spawn("Loading resources", move || {
+            JobResult::Resources(origin, kube.list_resources(&kind.name, namespace.as_deref()))
+        });
+    }
+
+    pub fn selected_resource_name(&self) -> Option<String> {
+        let selected = self.unfiltered_index(self.resource_list_state.selected())?;
+        self.resources.get(selected).cloned()
+    }
+
+    /// Asks for confirmation before deleting `name`.
+    pub fn confirm_delete(&mut self, kind: &str, name: &str, namespaced: bool) {
+        self.confirm_delete_all(kind, vec![name.to_string()], namespaced);
+    }
+
+    pub fn confirm_delete_all(&mut self, kind: &str, names: Vec<String>, namespaced: bool) {
+        let production = self.selected_context.as_deref().filter(|c| delete::is_production(c));
+        self.pending_delete = Some(PendingDelete::new(
+            kind,
+            names,
+            namespaced,
+            self.state,
+            production,
+        ));
+        self.state = AppState::ConfirmDelete;
+    }
+
+    pub fn cancel_delete(&mut self) {
+        if let Some(pending) = self.pending_delete.take() {
+            self.state = pending.return_to;
+        }
+    }
+
+    /// Deletes the confirmed resource in the background and returns to the
+    /// list it was picked from.
+    pub fn delete_confirmed(&mut self) {
+        let Some(pending) = self.pending_delete.take() else {
+            return;
+        };
+        if !pending.confirmed() {
+            self.status_message = Some(format!(
+                "Type {} to confirm",
+                pending.confirm_text.as_deref().unwrap_or_default()
+            ));
+            self.pending_delete = Some(pending);
+            return;
+        }
+        self.state = pending.return_to;
+
+        let items: Vec<(String, Vec<String>)> = pending
+            .names
+            .iter()
+            .map(|name| {
+                let args = pending.args(name);
+                let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+                let args = match pending.kind.as_str() {
+                    "pod" => self.pod_args(&args, name),
+                    _ => self.resource_args(&args, pending.namespaced),
+                };
+                (name.clone(), args)
+            })
+            .collect();
+        match items.as_slice() {
+            [(_, args)] => self.run_action(&format!("Deleting {}", pending.target()), args.clone()),
+            _ => self.run_bulk(&format!("Delete {}", pending.kind), items),
+        }
+    }
+
+    /// Runs one kubectl command per item in the background and shows which
+    /// of them failed once all are done.
+    pub fn run_bulk(&mut self, label: &str, items: Vec<(String, Vec<String>)>) {
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let description = label.to_string();
+        self.worker.spawn(label, move || {
+            JobResult::Bulk(origin, description, bulk::run_each(kube.as_ref(), &items))
+        });
+    }
+
+    /// The list screen being shown, including behind the delete dialog.
+    pub fn list_screen(&self) -> AppState {
+        match (&self.pending_delete, self.state) {
+            (Some(pending), AppState::ConfirmDelete) => pending.return_to,
+            (_, state) => state,
+        }
+    }
+
+    pub fn is_pod_list(&self) -> bool {
+        matches!(
+            self.list_screen(),
+            AppState::ExecPodSelection
+                | AppState::PodSelection
+                | AppState::DebugPodSelection
+                | AppState::NodePodList
+        )
+    }
+
+    /// Whether `/` can filter the current screen.
+    pub fn is_filterable(&self) -> bool {
+        matches!(
+            self.state,
+            AppState::NamespaceSelection
+                | AppState::ContextSelection
+                | AppState::ExecPodSelection
+                | AppState::PodSelection
+                | AppState::DebugPodSelection
+                | AppState::NodePodList
+                | AppState::ResourceList
+        )
+    }
+
+    /// Names in the list on screen before filtering, in display order.
+    pub fn list_names(&self) -> Vec<String> {
+        match self.list_screen() {
+            AppState::NamespaceSelection => self.namespaces.clone(),
+            AppState::ContextSelection => self.contexts.clone(),
+            AppState::ResourceList => self.resources.clone(),
+            AppState::DeploymentList => self.deployments.iter().map(|d| d.name.clone()).collect(),
+            state if state.workload_kind().is_some() => {
+                self.workloads.iter().map(|w| w.name.clone()).collect()
+            }
+            _ if self.is_pod_list() => self.pods.iter().map(|p| p.name.clone()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The fuzzy query narrowing the list on screen, or "" if there is none.
+    /// Queries containing `=` are label selectors in the pod lists.
+    pub fn fuzzy_query(&self) -> &str {
+        let selector = self.is_pod_list() && self.filter.selector().is_some();
+        if self.filter.applies_to(self.list_screen()) && !selector {
+            &self.filter.query
         } else {
-            Err("Failed to load contexts".into())
+            ""
         }
     }
 
-    pub fn load_pods(&mut self) -> Result<(), Box<dyn Error>> {
-        let namespace = self.current_namespace();
-        let output = Command::new("kubectl")
-            .args(&["get", "pods", "-n", &namespace, "-o=jsonpath='{.items[*].metadata.name}'"])
-            .output()?;
-
-        if output.status.success() {
-            let pod_output = String::from_utf8_lossy(&output.stdout)
-                .trim_matches('\'')
-                .to_string();
-            self.pods = pod_output
-                .split_whitespace()
-                .map(|s| s.to_string())
-                .collect();
-            self.pod_list_state.select(Some(0));
-            Ok(())
+    /// Positions in `list_names` of the rows shown, in display order. The
+    /// list's selection indexes into these.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        if !self.filter.applies_to(self.list_screen()) {
+            return (0..self.list_names().len()).collect();
+        }
+        match self.filter.selector() {
+            Some(selector) if self.is_pod_list() => (0..self.pods.len())
+                .filter(|&i| selector.matches(&self.pods[i].labels))
+                .collect(),
+            _ => {
+                let query = &self.filter.query;
+                let names = self.list_names();
+                (0..names.len())
+                    .filter(|&i| filter::fuzzy_positions(query, &names[i]).is_some())
+                    .collect()
+            }
+        }
+    }
+
+    /// Maps a selected row of the list on screen to its position in the
+    /// unfiltered list.
+    fn unfiltered_index(&self, row: Option<usize>) -> Option<usize> {
+        let row = row.unwrap_or(0);
+        if self.filter.applies_to(self.list_screen()) {
+            self.visible_indices().get(row).copied()
         } else {
-            let error_msg = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Failed to get pods: {}", error_msg).into())
+            Some(row)
         }
     }
 
-    pub fn switch_context(&mut self, context: &str) -> Result<(), Box<dyn Error>> {
-        let status = Command::new("kubectl")
-            .args(&["config", "use-context", context])
-            .status()?;
+    fn list_screen_state(&mut self) -> Option<&mut ListState> {
+        match self.list_screen() {
+            AppState::NamespaceSelection => Some(&mut self.namespace_list_state),
+            AppState::ContextSelection => Some(&mut self.context_list_state),
+            AppState::ResourceList => Some(&mut self.resource_list_state),
+            _ => None,
+        }
+    }
+
+    fn select_row(&mut self, row: usize) {
+        if self.is_pod_list() {
+            self.pod_list_state.select(Some(row));
+        } else if let Some(state) = self.list_screen_state() {
+            state.select(Some(row));
+        }
+    }
+
+    /// Highlights `name` if it is shown, or else the first row.
+    fn select_name(&mut self, name: Option<String>) {
+        let names = self.list_names();
+        let row = name
+            .and_then(|name| {
+                self.visible_indices()
+                    .into_iter()
+                    .position(|i| names[i] == name)
+            })
+            .unwrap_or(0);
+        self.select_row(row);
+    }
+
+    /// The highlighted name in the list on screen.
+    pub fn highlighted_name(&self) -> Option<String> {
+        let row = match self.list_screen() {
+            AppState::NamespaceSelection => self.namespace_list_state.selected(),
+            AppState::ContextSelection => self.context_list_state.selected(),
+            AppState::ResourceList => self.resource_list_state.selected(),
+            AppState::DeploymentList => self.deployment_list_state.selected(),
+            state if state.workload_kind().is_some() => self.workload_list_state.selected(),
+            _ if self.is_pod_list() => self.pod_list_state.selected(),
+            _ => return None,
+        };
+        let index = self.unfiltered_index(row)?;
+        self.list_names().into_iter().nth(index)
+    }
+
+    /// `title` with the filter query and how many rows it lets through.
+    pub fn filter_title(&self, title: &str) -> String {
+        let shown = self.visible_indices().len();
+        let total = self.list_names().len();
+        self.filter.title(title, self.list_screen(), shown, total)
+    }
+
+    pub fn open_filter(&mut self) {
+        self.filter.open(self.state);
+    }
+
+    pub fn is_filtering(&self) -> bool {
+        self.filter.editing && self.filter.list == Some(self.state)
+    }
+
+    /// Narrows the list as the query is typed, keeping the highlighted item
+    /// selected while it still matches.
+    pub fn filter_push(&mut self, c: char) {
+        let selected = self.highlighted_name();
+        self.filter.query.push(c);
+        self.select_name(selected);
+    }
+
+    pub fn filter_pop(&mut self) {
+        let selected = self.highlighted_name();
+        self.filter.query.pop();
+        self.select_name(selected);
+    }
 
-        if status.success() {
-            self.selected_context = Some(context.to_string());
-            Ok(())
+    /// Stops typing but keeps the list narrowed.
+    pub fn finish_filter(&mut self) {
+        if self.filter.query.is_empty() {
+            self.clear_filter();
         } else {
-            Err("Failed to switch context".into())
+            self.filter.editing = false;
         }
     }
 
-    pub fn execute_kubectl(&mut self, args: &[&str]) -> Result<(), Box<dyn Error>> {
-        let output = Command::new("kubectl")
-            .args(args)
-            .output()?;
+    pub fn clear_filter(&mut self) {
+        let selected = self.highlighted_name();
+        self.filter.clear();
+        self.select_name(selected);
+    }
+
+    /// Names of the rows shown, in display order.
+    pub fn visible_names(&self) -> Vec<String> {
+        let names = self.list_names();
+        self.visible_indices()
+            .into_iter()
+            .map(|i| names[i].clone())
+            .collect()
+    }
+
+    pub fn toggle_mark(&mut self) {
+        if let Some(name) = self.highlighted_name() {
+            if !self.marked.remove(&name) {
+                self.marked.insert(name);
+            }
+        }
+    }
+
+    /// Marks every item shown, or clears the marks if all of them are
+    /// marked already.
+    pub fn mark_all(&mut self) {
+        let names = self.visible_names();
+        if names.iter().all(|n| self.marked.contains(n)) {
+            self.marked.clear();
+        } else {
+            self.marked.extend(names);
+        }
+    }
+
+    /// Inverts the marks of the items shown.
+    pub fn invert_marks(&mut self) {
+        for name in self.visible_names() {
+            if !self.marked.remove(&name) {
+                self.marked.insert(name);
+            }
+        }
+    }
 
-        self.output = if output.status.success() {
-            String::from_utf8_lossy(&output.stdout).to_string()
+    /// The marked items in list order, or the highlighted one if none are.
+    pub fn targets(&self) -> Vec<String> {
+        if self.marked.is_empty() {
+            self.highlighted_name().into_iter().collect()
         } else {
-            String::from_utf8_lossy(&output.stderr).to_string()
+            self.list_names()
+                .into_iter()
+                .filter(|n| self.marked.contains(n))
+                .collect()
+        }
+    }
+
+    pub fn describe_all(&mut self, kind: &str, names: &[String], namespaced: bool) {
+        let mut args = vec!["describe", kind];
+        args.extend(names.iter().map(|n| n.as_str()));
+        let args = self.resource_args(&args, namespaced);
+        self.spawn_resource_job("Describing", args, kind, &names.join(", "), JobResult::Describe);
+    }
+
+    /// Restarts the Deployment, StatefulSet or DaemonSet behind each target
+    /// pod, once per owner.
+    pub fn restart_owners(&mut self) {
+        let pods: Vec<_> = self
+            .targets()
+            .into_iter()
+            .filter_map(|name| self.pods.iter().find(|p| p.name == name))
+            .map(|p| (p.name.clone(), self.pod_namespace(&p.name), p.owner.clone()))
+            .collect();
+        let kube = self.kube.clone();
+        let origin = self.state;
+        self.worker.spawn("Restarting owners", move || {
+            let mut restarted = Vec::new();
+            let results = pods
+                .into_iter()
+                .map(|(pod, namespace, owner)| {
+                    let result =
+                        bulk::restart_target(kube.as_ref(), &namespace, owner.as_ref()).and_then(
+                            |target| {
+                                let key = format!("{}/{}", namespace, target);
+                                if restarted.contains(&key) {
+                                    return Ok(format!("{} already restarted", target));
+                                }
+                                kube.run(&["rollout", "restart", &target, "-n", &namespace])?;
+                                restarted.push(key);
+                                Ok(format!("restarted {}", target))
+                            },
+                        );
+                    (pod, result.map_err(|e| e.trim().to_string()))
+                })
+                .collect();
+            JobResult::Bulk(origin, "Restart owners".to_string(), results)
+        });
+    }
+
+    /// Fetches the recent logs of every target pod into one foldable view.
+    pub fn fetch_logs(&mut self) {
+        let pods = self.targets();
+        let items: Vec<(String, Vec<String>)> = pods
+            .iter()
+            .map(|pod| {
+                let namespace = self.pod_namespace(pod);
+                let args = ["logs", "--tail=100", "--all-containers=true", "-n", &namespace, pod];
+                (pod.clone(), args.iter().map(|a| a.to_string()).collect())
+            })
+            .collect();
+        let kube = self.kube.clone();
+        let origin = self.state;
+        self.worker.spawn("Fetching logs", move || {
+            JobResult::Logs(origin, pods, bulk::run_each(kube.as_ref(), &items))
+        });
+    }
+
+    pub fn close_bulk_summary(&mut self) {
+        if let Some(summary) = self.bulk.take() {
+            self.state = summary.return_to;
+            self.reload_list();
+        }
+    }
+
+    /// Runs a kubectl command that changes the cluster. Its output ends up in
+    /// the status bar and the list on screen is reloaded afterwards.
+    pub fn run_action(&mut self, label: &str, args: Vec<String>) {
+        let kube = self.kube.clone();
+        let description = label.to_string();
+        self.worker.spawn(label, move || {
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            JobResult::Action(description, kube.run(&args))
+        });
+    }
+
+    fn reload_list(&mut self) {
+        match self.state {
+            AppState::ResourceList => self.load_resources(),
+            AppState::DeploymentList => self.load_deployments(),
+            AppState::NodeList => self.load_nodes(),
+            AppState::NodePodList => self.load_node_pods(),
+            AppState::SecretList => self.load_secrets(),
+            AppState::ExecPodSelection | AppState::PodSelection | AppState::DebugPodSelection => {
+                self.load_pods(self.state)
+            }
+            state => {
+                if let Some(kind) = state.workload_kind() {
+                    self.load_workloads(kind);
+                }
+            }
+        }
+    }
+
+    pub fn load_deployments(&mut self) {
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let namespace = self.current_namespace();
+        self.worker.spawn("Loading deployments", move || {
+            JobResult::Deployments(origin, kube.list_deployments(&namespace))
+        });
+    }
+
+    pub fn selected_deployment(&self) -> Option<&Deployment> {
+        let selected = self.deployment_list_state.selected().unwrap_or(0);
+        self.deployments.get(selected)
+    }
+
+    pub fn open_scale_input(&mut self) {
+        if let Some(deployment) = self.selected_deployment() {
+            self.scale_input = deployment.desired.to_string();
+            self.state = AppState::ScaleInput;
+        }
+    }
+
+    pub fn scale_selected(&mut self) {
+        let Some(name) = self.selected_deployment().map(|d| d.name.clone()) else {
+            return;
         };
-        Ok(())
+        let Ok(replicas) = self.scale_input.trim().parse::<u32>() else {
+            self.status_message = Some("Replica count must be a whole number".to_string());
+            return;
+        };
+
+        self.state = AppState::DeploymentList;
+        let args = self.resource_args(
+            &["scale", &format!("deployment/{}", name), &format!("--replicas={}", replicas)],
+            true,
+        );
+        self.run_action(&format!("Scaling {} to {}", name, replicas), args);
+    }
+
+    pub fn restart_selected(&mut self) {
+        self.rollout_restart("deployment");
+    }
+
+    /// Restarts the marked items of the list, or the highlighted one.
+    fn rollout_restart(&mut self, kind: &str) {
+        let items: Vec<(String, Vec<String>)> = self
+            .targets()
+            .into_iter()
+            .map(|name| {
+                let target = format!("{}/{}", kind, name);
+                (name, self.resource_args(&["rollout", "restart", &target], true))
+            })
+            .collect();
+        match items.as_slice() {
+            [] => {}
+            [(name, args)] => {
+                self.run_action(&format!("Restarting {}/{}", kind, name), args.clone())
+            }
+            _ => self.run_bulk(&format!("Restart {}", kind), items),
+        }
     }
 
-    pub fn copy_pod(&mut self, original_pod: &str, new_pod_name: &str) -> Result<(), Box<dyn Error>> {
+    pub fn load_workloads(&mut self, kind: WorkloadKind) {
+        let kube = self.kube.clone();
+        let origin = self.state;
         let namespace = self.current_namespace();
-        let output = Command::new("kubectl")
-            .args(&[
-                "debug",
-                "-it",
+        self.worker.spawn(&format!("Loading {}", kind.title()), move || {
+            JobResult::Workloads(origin, kind, kube.list_workloads(kind, &namespace))
+        });
+    }
+
+    pub fn selected_workload(&self) -> Option<&Workload> {
+        let selected = self.workload_list_state.selected().unwrap_or(0);
+        self.workloads.get(selected)
+    }
+
+    pub fn restart_workload(&mut self, kind: WorkloadKind) {
+        if kind.restartable() {
+            self.rollout_restart(kind.resource());
+        }
+    }
+
+    /// Suspends the selected cronjob, or resumes it if it is suspended.
+    pub fn toggle_cronjob_suspend(&mut self) {
+        let Some((name, suspended)) = self.selected_workload().map(|w| (w.name.clone(), w.suspended))
+        else {
+            return;
+        };
+        let patch = format!("{{\"spec\":{{\"suspend\":{}}}}}", !suspended);
+        let args = self.resource_args(
+            &["patch", &format!("cronjob/{}", name), "--type=merge", "-p", &patch],
+            true,
+        );
+        let verb = if suspended { "Resuming" } else { "Suspending" };
+        self.run_action(&format!("{} cronjob/{}", verb, name), args);
+    }
+
+    /// Starts a job from the selected cronjob's template right away.
+    pub fn trigger_cronjob(&mut self) {
+        let Some(name) = self.selected_workload().map(|w| w.name.clone()) else {
+            return;
+        };
+        let job = workloads::manual_job_name(&name, pods::now_timestamp());
+        let args = self.resource_args(
+            &["create", "job", &job, &format!("--from=cronjob/{}", name)],
+            true,
+        );
+        self.run_action(&format!("Triggering cronjob/{}", name), args);
+    }
+
+    /// Asks to delete every job that has completed or failed.
+    pub fn delete_finished_jobs(&mut self) {
+        let finished: Vec<String> = self
+            .workloads
+            .iter()
+            .filter(|w| w.finished)
+            .map(|w| w.name.clone())
+            .collect();
+        if finished.is_empty() {
+            self.status_message = Some("No finished jobs".to_string());
+        } else {
+            self.confirm_delete_all("job", finished, true);
+        }
+    }
+
+    /// Shows the events of the current namespace, only those about `object`
+    /// if given, and returns to the current screen afterwards.
+    pub fn open_events(&mut self, object: Option<InvolvedObject>) {
+        self.event_object = object;
+        self.events_return = self.state;
+        self.load_events();
+    }
+
+    pub fn open_pod_events(&mut self) {
+        if let Some(pod) = self.selected_pod_name() {
+            let namespace = self.pod_namespace(&pod);
+            // Pods on a node can live outside the current namespace.
+            if namespace != self.current_namespace() {
+                self.saved_events_all_namespaces = Some(self.events_all_namespaces);
+                self.events_all_namespaces = true;
+            }
+            self.open_events(Some(InvolvedObject {
+                namespace,
+                kind: "Pod".to_string(),
+                name: pod,
+            }));
+        }
+    }
+
+    pub fn load_events(&mut self) {
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let namespace = (!self.events_all_namespaces).then(|| self.current_namespace());
+        self.worker.spawn("Loading events", move || {
+            JobResult::Events(origin, kube.list_events(namespace.as_deref()))
+        });
+    }
+
+    /// Events about `event_object`, or all of them, newest first.
+    pub fn visible_events(&self) -> Vec<&Event> {
+        self.events
+            .iter()
+            .filter(|e| self.event_object.as_ref().is_none_or(|o| o.matches(e)))
+            .collect()
+    }
+
+    pub fn selected_event(&self) -> Option<&Event> {
+        let selected = self.event_list_state.selected().unwrap_or(0);
+        self.visible_events().get(selected).copied()
+    }
+
+    /// Narrows the list to the highlighted event's object, or shows every
+    /// object again if it is narrowed already.
+    pub fn toggle_event_object(&mut self) {
+        if self.event_object.take().is_none() {
+            self.event_object = self.selected_event().map(InvolvedObject::of);
+        }
+        self.event_list_state.select(Some(0));
+    }
+
+    pub fn toggle_events_all_namespaces(&mut self) {
+        self.events_all_namespaces = !self.events_all_namespaces;
+        self.load_events();
+    }
+
+    pub fn close_events(&mut self) {
+        if let Some(all_namespaces) = self.saved_events_all_namespaces.take() {
+            self.events_all_namespaces = all_namespaces;
+        }
+        self.state = self.events_return;
+    }
+
+    /// Fills the main menu preview with the newest warnings in the namespace.
+    pub fn preview_warnings(&mut self) {
+        let kube = self.kube.clone();
+        let namespace = self.current_namespace();
+        self.worker.spawn("Loading warnings", move || {
+            JobResult::Output(match kube.list_events(Some(&namespace)) {
+                Ok(events) => {
+                    let warnings = events::warning_summary(&events, 20);
+                    if warnings.is_empty() {
+                        format!("No warnings in {}", namespace)
+                    } else {
+                        warnings.join("\n")
+                    }
+                }
+                Err(e) => e,
+            })
+        });
+    }
+
+    pub fn load_rollout_history(&mut self) {
+        let Some(name) = self.selected_deployment().map(|d| d.name.clone()) else {
+            return;
+        };
+        let args = self.resource_args(&["rollout", "history", &format!("deployment/{}", name)], true);
+        let kube = self.kube.clone();
+        self.worker.spawn("Loading rollout history", move || {
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            JobResult::RolloutHistory(kube.run(&args))
+        });
+    }
+
+    pub fn undo_to_selected_revision(&mut self) {
+        let selected = self.revision_list_state.selected().unwrap_or(0);
+        let (Some(name), Some(revision)) = (
+            self.selected_deployment().map(|d| d.name.clone()),
+            self.revisions.get(selected).map(|r| r.number),
+        ) else {
+            return;
+        };
+
+        self.state = AppState::DeploymentList;
+        let args = self.resource_args(
+            &[
+                "rollout",
+                "undo",
+                &format!("deployment/{}", name),
+                &format!("--to-revision={}", revision),
+            ],
+            true,
+        );
+        self.run_action(&format!("Rolling back {} to revision {}", name, revision), args);
+    }
+
+    pub fn watch_rollout(&mut self) {
+        if let Some(name) = self.selected_deployment().map(|d| d.name.clone()) {
+            self.rollout = Some(RolloutWatch::new(&name));
+            self.state = AppState::RolloutStatus;
+            self.poll_rollout();
+        }
+    }
+
+    pub fn close_rollout(&mut self) {
+        self.rollout = None;
+        self.state = AppState::DeploymentList;
+        self.load_deployments();
+    }
+
+    /// Refreshes the rollout status about once a second until it completes.
+    /// Called from the event loop on every tick.
+    pub fn poll_rollout(&mut self) {
+        let tick = self.tick;
+        let namespace = self.current_namespace();
+        let Some(rollout) = self.rollout.as_mut() else {
+            return;
+        };
+        let due = rollout.last_poll.is_none_or(|last| tick.wrapping_sub(last) >= 10);
+        if rollout.done || rollout.polling || !due {
+            return;
+        }
+        rollout.polling = true;
+        rollout.last_poll = Some(tick);
+
+        let kube = self.kube.clone();
+        let target = format!("deployment/{}", rollout.deployment);
+        self.worker.spawn("Checking rollout", move || {
+            let status = kube.run(&[
+                "rollout",
+                "status",
+                &target,
                 "-n",
                 &namespace,
-                original_pod,
-                "--copy-to",
-                new_pod_name,
-                "--container=worker",
-                "--",
-                "bash",
-            ])
-            .output()?;
-
-        if output.status.success() {
-            self.output = String::from_utf8_lossy(&output.stdout).to_string();
-        } else {
-            self.output = String::from_utf8_lossy(&output.stderr).to_string();
+                "--watch=false",
+            ]);
+            JobResult::RolloutStatus(status, kube.list_deployments(&namespace))
+        });
+    }
+
+    pub fn selected_container_name(&self) -> Option<String> {
+        let selected = self.container_list_state.selected().unwrap_or(0);
+        self.containers.get(selected).map(|c| c.name.clone())
+    }
+
+    pub fn open_logs(&mut self, pod: &str, container: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let namespace = self.pod_namespace(pod);
+        self.logs = Some(LogView::new(self.kube.clone(), &namespace, pod, container)?);
+        self.state = AppState::PodLogs;
+        Ok(())
+    }
+
+    pub fn close_logs(&mut self) {
+        self.logs = None;
+        self.state = self.pod_return;
+    }
+
+    pub fn switch_context(&mut self, context: &str) -> Result<(), Box<dyn Error>> {
+        self.kube.switch_context(context)?;
+        self.selected_context = Some(context.to_string());
+        // The new cluster may well have a metrics API.
+        self.metrics.reset();
+        Ok(())
+    }
+
+    pub fn execute_kubectl(&mut self, args: &[&str]) {
+        let kube = self.kube.clone();
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        self.worker.spawn("Running kubectl", move || {
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            JobResult::Output(kube.run(&args).unwrap_or_else(|e| e))
+        });
+    }
+
+    /// Runs `kubectl describe` for one resource in the background. Cluster-scoped
+    /// kinds such as namespaces are described without `-n`.
+    pub fn describe(&mut self, kind: &str, name: &str, namespaced: bool) {
+        let args = self.resource_args(&["describe", kind, name], namespaced);
+        self.spawn_resource_job("Describing", args, kind, name, JobResult::Describe);
+    }
+
+    pub fn close_describe(&mut self) {
+        self.describe = None;
+        self.state = self.detail_return;
+    }
+
+    /// Fetches a resource's YAML in the background for the manifest view.
+    pub fn view_manifest(&mut self, kind: &str, name: &str, namespaced: bool) {
+        let args = self.resource_args(&["get", kind, name, "-o", "yaml"], namespaced);
+        self.spawn_resource_job("Fetching YAML", args, kind, name, JobResult::Manifest);
+    }
+
+    pub fn close_manifest(&mut self) {
+        self.manifest = None;
+        self.state = self.detail_return;
+    }
+
+    fn resource_args(&self, args: &[&str], namespaced: bool) -> Vec<String> {
+        let mut args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        if namespaced {
+            args.extend(["-n".to_string(), self.current_namespace()]);
+        }
+        args
+    }
+
+    /// Hands the manifest being viewed to `$EDITOR`.
+    pub fn edit_manifest(&mut self) {
+        if let Some(view) = &self.manifest {
+            self.interactive = Some(Interactive::Edit {
+                kind: view.kind.clone(),
+                name: view.name.clone(),
+                yaml: view.editable_yaml(),
+            });
+        }
+    }
+
+    /// Writes `yaml` to a temp file, opens the editor on it and, if it was
+    /// changed, diffs it against the cluster in the background.
+    fn edit_in_editor(&mut self, kind: &str, name: &str, yaml: &str) -> Result<(), Box<dyn Error>> {
+        let path = edit::write_temp(kind, name, yaml)?;
+
+        let changed = match edit::run_editor(&self.editor, &path) {
+            Ok(changed) => changed,
+            Err(e) => {
+                let _ = std::fs::remove_file(&path);
+                return Err(e);
+            }
+        };
+        if !changed {
+            let _ = std::fs::remove_file(&path);
+            self.status_message = Some(format!("No changes made to {}/{}", kind, name));
+            self.state = AppState::Manifest;
+            return Ok(());
         }
-        self.state = AppState::ShowOutput;
+
+        let pending = PendingEdit {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            path,
+            diff: Vec::new(),
+            scroll: 0,
+            page_height: 0,
+        };
+        let kube = self.kube.clone();
+        let origin = self.state;
+        self.worker.spawn("Diffing", move || {
+            let result = kube.diff(&pending.path.to_string_lossy());
+            JobResult::EditDiff(origin, pending, result)
+        });
         Ok(())
     }
 
-    pub fn exec_pod(&mut self, pod: &str) -> Result<(), Box<dyn Error>> {
+    /// Applies the confirmed edit. The apply cannot be cancelled, so the
+    /// screen waits for its outcome.
+    pub fn apply_edit(&mut self) {
+        let Some(pending) = self.pending_edit.take() else {
+            return;
+        };
+        let kube = self.kube.clone();
+        self.state = AppState::Applying;
+        self.worker.spawn_background("Applying", move || {
+            let path = pending.path.to_string_lossy().to_string();
+            let result = kube.run(&["apply", "-f", &path]);
+            // Dropping the edit removes the temp file once it is applied.
+            drop(pending);
+            JobResult::Finished(AppState::Applying, result)
+        });
+    }
+
+    pub fn discard_edit(&mut self) {
+        if let Some(pending) = self.pending_edit.take() {
+            self.status_message = Some(format!(
+                "Discarded changes to {}/{}",
+                pending.kind, pending.name
+            ));
+        }
+        self.state = AppState::Manifest;
+    }
+
+    /// Runs kubectl for one resource and wraps the output with `result`,
+    /// tagged with the screen that asked for it.
+    fn spawn_resource_job(
+        &mut self,
+        label: &str,
+        args: Vec<String>,
+        kind: &str,
+        name: &str,
+        result: fn(AppState, String, String, Result<String, String>) -> JobResult,
+    ) {
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let kind = kind.to_string();
+        let name = name.to_string();
+        self.worker.spawn(label, move || {
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            result(origin, kind, name, kube.run(&args))
+        });
+    }
+
+    pub fn poll_jobs(&mut self) {
+        for result in self.worker.poll() {
+            self.apply_job_result(result);
+        }
+    }
+
+    /// Creates the copy described by `copy_form`, attaching to it if asked.
+    pub fn copy_pod(&mut self) {
+        let args = self.copy_form.args(&self.current_namespace());
+        if self.copy_form.attach {
+            self.interactive = Some(Interactive::Debug {
+                pod: self.copy_form.new_name.trim().to_string(),
+                args,
+                return_to: AppState::PodSelection,
+            });
+            return;
+        }
+
+        let kube = self.kube.clone();
+        let origin = self.state;
+        self.worker.spawn("Copying pod", move || {
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            JobResult::Finished(origin, kube.run(&args))
+        });
+    }
+
+    pub fn exec_pod(&mut self, pod: &str, container: Option<&str>, command: &[&str]) {
+        self.interactive = Some(Interactive::Exec {
+            pod: pod.to_string(),
+            container: container.map(|c| c.to_string()),
+            command: command.iter().map(|c| c.to_string()).collect(),
+        });
+    }
+
+    /// Starts an exec session with the shell chosen in the shell picker.
+    /// Returns false if the user still needs to type a custom command.
+    pub fn exec_with_shell(&mut self, shell: Shell) -> bool {
+        let Some(pod) = self.selected_pod.clone() else {
+            return true;
+        };
+        let container = self.selected_container.clone();
+
+        let command = match shell.command() {
+            Some(command) => vec![command.to_string()],
+            None if self.exec_command.trim().is_empty() => return false,
+            None => self
+                .exec_command
+                .split_whitespace()
+                .map(|s| s.to_string())
+                .collect(),
+        };
+        let command: Vec<&str> = command.iter().map(|c| c.as_str()).collect();
+        self.exec_pod(&pod, container.as_deref(), &command);
+        true
+    }
+
+    /// Runs an interactive session. The caller is responsible for handing the
+    /// terminal over beforehand and restoring it afterwards.
+    pub fn run_interactive(&mut self, session: Interactive) {
+        match session {
+            Interactive::Exec {
+                pod,
+                container,
+                mut command,
+            } => {
+                let namespace = self.pod_namespace(&pod);
+                // Many images ship without bash, so probe for it before attaching.
+                let container_name = container.as_deref();
+                let mut fallback = "";
+                if command == ["bash"]
+                    && self
+                        .kube
+                        .exec_output(&namespace, &pod, container_name, &["bash", "-c", "true"])
+                        .is_err()
+                {
+                    command = vec!["sh".to_string()];
+                    fallback = " (bash not found, used sh)";
+                }
+
+                let command: Vec<&str> = command.iter().map(|c| c.as_str()).collect();
+                match self.kube.exec(&namespace, &pod, container_name, &command) {
+                    Ok(code) => {
+                        self.status_message = Some(format!(
+                            "Exec session in {} exited with code {}{}",
+                            pod, code, fallback
+                        ));
+                        self.state = self.pod_return;
+                    }
+                    Err(e) => self.show_message(format!("Error exec into pod: {}", e)),
+                }
+            }
+            Interactive::Edit { kind, name, yaml } => {
+                if let Err(e) = self.edit_in_editor(&kind, &name, &yaml) {
+                    self.show_message(format!("Error editing {}/{}: {}", kind, name, e));
+                }
+            }
+            Interactive::Debug {
+                pod,
+                args,
+                return_to,
+            } => {
+                let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+                match self.kube.run_attached(&args) {
+                    Ok(code) => {
+                        self.status_message =
+                            Some(format!("Debug session in {} exited with code {}", pod, code));
+                        self.state = return_to;
+                    }
+                    Err(e) => self.show_message(format!("Error debugging pod: {}", e)),
+                }
+            }
+        }
+    }
+
+    /// Applies a finished background job. Screen transitions only happen if the
+    /// user is still on the screen that issued the request.
+    fn apply_job_result(&mut self, result: JobResult) {
+        match result {
+            JobResult::Namespaces(origin, result) => {
+                if self.state != origin {
+                    return;
+                }
+                let reload = origin == AppState::NamespaceSelection;
+                match result {
+                    Ok(namespaces) => {
+                        let selected = self.highlighted_name().filter(|_| reload);
+                        self.watch.record(&self.namespaces, &namespaces, |n| n, reload);
+                        self.namespaces = namespaces;
+                        if !reload {
+                            self.filter.clear();
+                        }
+                        self.state = AppState::NamespaceSelection;
+                        self.select_name(selected);
+                    }
+                    Err(e) if reload => {
+                        self.status_message = Some(format!("Error refreshing namespaces: {}", e))
+                    }
+                    Err(e) => self.show_message(format!("Error loading namespaces: {}", e)),
+                }
+            }
+            JobResult::Contexts(result) => {
+                if self.state != AppState::MainMenu {
+                    return;
+                }
+                match result {
+                    Ok(contexts) => {
+                        self.contexts = contexts;
+                        self.filter.clear();
+                        self.context_list_state.select(Some(0));
+                        self.state = AppState::ContextSelection;
+                    }
+                    Err(e) => self.show_message(format!("Error loading contexts: {}", e)),
+                }
+            }
+            JobResult::Pods(next, result) => {
+                // Pods load from the main menu, or reload in place.
+                if self.state != AppState::MainMenu && self.state != next {
+                    return;
+                }
+                let reload = self.state == next;
+                match result {
+                    Ok(mut pods) => {
+                        let selected = self.selected_pod_name().filter(|_| reload);
+                        pods::sort_pods(&mut pods, self.pod_sort, self.pod_sort_descending);
+                        self.watch.record(&self.pods, &pods, |p| &p.name, reload);
+                        self.pods = pods;
+                        if reload {
+                            self.marked.retain(|n| self.pods.iter().any(|p| &p.name == n));
+                        } else {
+                            self.marked.clear();
+                            self.filter.clear();
+                        }
+                        self.state = next;
+                        self.select_name(selected);
+                        self.load_pod_metrics(Some(self.current_namespace()));
+                    }
+                    Err(e) if reload => {
+                        self.status_message = Some(format!("Error refreshing pods: {}", e))
+                    }
+                    Err(e) => self.show_message(format!("Error loading pods: {}", e)),
+                }
+            }
+            JobResult::Containers(origin, action, pod, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(containers) => {
+                        self.containers = containers;
+                        self.container_list_state.select(Some(0));
+                        self.selected_pod = Some(pod.clone());
+                        self.pod_return = origin;
+                        match action {
+                            ContainerAction::Logs => self.open_logs_or_pick_container(&pod),
+                            ContainerAction::Exec => self.pick_exec_container(),
+                            ContainerAction::Copy => self.open_copy_form(&pod),
+                            ContainerAction::Debug => self.pick_debug_target(),
+                        }
+                    }
+                    Err(e) => self.show_message(format!("Error loading containers: {}", e)),
+                }
+            }
+            JobResult::ApiResources(result) => {
+                if self.state != AppState::MainMenu {
+                    return;
+                }
+                match result {
+                    Ok(resources) => {
+                        self.api_resources = resources;
+                        self.api_resource_list_state.select(Some(0));
+                        self.state = AppState::ResourceKindSelection;
+                    }
+                    Err(e) => self.show_message(format!("Error loading API resources: {}", e)),
+                }
+            }
+            JobResult::Resources(origin, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(resources) => {
+                        let selected = self.resource_list_state.selected().unwrap_or(0);
+                        let reload = origin == AppState::ResourceList;
+                        self.resources = resources;
+                        if reload {
+                            self.marked.retain(|n| self.resources.contains(n));
+                        } else {
+                            self.marked.clear();
+                            self.filter.clear();
+                        }
+                        self.state = AppState::ResourceList;
+                        let last = self.visible_indices().len().saturating_sub(1);
+                        self.resource_list_state
+                            .select(Some(if reload { selected.min(last) } else { 0 }));
+                    }
+                    Err(e) => self.show_message(format!("Error loading resources: {}", e)),
+                }
+            }
+            JobResult::Action(label, result) => {
+                self.status_message = Some(match result {
+                    Ok(output) => output.trim().to_string(),
+                    Err(e) => format!("{} failed: {}", label, e.trim()),
+                });
+                self.reload_list();
+            }
+            JobResult::Bulk(origin, label, results) => {
+                if self.state != origin {
+                    return;
+                }
+                self.marked.clear();
+                self.bulk = Some(BulkSummary {
+                    label,
+                    results,
+                    return_to: origin,
+                });
+                self.state = AppState::BulkSummary;
+            }
+            JobResult::Logs(origin, pods, results) => {
+                if self.state != origin {
+                    return;
+                }
+                let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+                if failed > 0 {
+                    self.status_message = Some(format!(
+                        "Fetched logs from {} of {} pods",
+                        results.len() - failed,
+                        results.len()
+                    ));
+                }
+                let logs = bulk::combine_logs(&results);
+                let mut view = DescribeView::new("pod", &pods.join(", "), &logs);
+                view.heading = "Logs".to_string();
+                self.describe = Some(view);
+                self.detail_return = origin;
+                self.state = AppState::Describe;
+            }
+            JobResult::Workloads(origin, kind, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(workloads) => {
+                        let selected = self.selected_workload().map(|w| w.name.clone());
+                        self.workloads = workloads;
+                        if origin == AppState::workload_list(kind) {
+                            self.marked.retain(|n| self.workloads.iter().any(|w| &w.name == n));
+                        } else {
+                            self.marked.clear();
+                        }
+                        let index = selected
+                            .and_then(|name| self.workloads.iter().position(|w| w.name == name))
+                            .unwrap_or(0);
+                        self.workload_list_state.select(Some(index));
+                        self.state = AppState::workload_list(kind);
+                    }
+                    Err(e) => self.show_message(format!("Error loading {}: {}", kind.title(), e)),
+                }
+            }
+            JobResult::Deployments(origin, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(deployments) => {
+                        let selected = self.selected_deployment().map(|d| d.name.clone());
+                        self.deployments = deployments;
+                        if origin == AppState::DeploymentList {
+                            self.marked.retain(|n| self.deployments.iter().any(|d| &d.name == n));
+                        } else {
+                            self.marked.clear();
+                        }
+                        let index = selected
+                            .and_then(|name| self.deployments.iter().position(|d| d.name == name))
+                            .unwrap_or(0);
+                        self.deployment_list_state.select(Some(index));
+                        self.state = AppState::DeploymentList;
+                    }
+                    Err(e) => self.show_message(format!("Error loading deployments: {}", e)),
+                }
+            }
+            JobResult::Events(origin, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(events) => {
+                        let selected = self.event_list_state.selected().unwrap_or(0);
+                        let reload = origin == AppState::EventList;
+                        self.events = events;
+                        let last = self.visible_events().len().saturating_sub(1);
+                        self.event_list_state
+                            .select(Some(if reload { selected.min(last) } else { 0 }));
+                        self.state = AppState::EventList;
+                    }
+                    Err(e) => self.show_message(format!("Error loading events: {}", e)),
+                }
+            }
+            JobResult::Nodes(origin, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(nodes) => {
+                        let selected = self.selected_node_name();
+                        self.nodes = nodes;
+                        let index = selected
+                            .and_then(|name| self.nodes.iter().position(|n| n.name == name))
+                            .unwrap_or(0);
+                        self.node_list_state.select(Some(index));
+                        self.state = AppState::NodeList;
+                        self.load_node_metrics();
+                    }
+                    Err(e) => self.show_message(format!("Error loading nodes: {}", e)),
+                }
+            }
+            JobResult::NodePods(origin, node, result) => {
+                if self.state != origin {
+                    return;
+                }
+                // Pods load from the node list, or reload after a bulk action.
+                let reload = origin == AppState::NodePodList;
+                match result {
+                    Ok(mut pods) => {
+                        let selected = self.selected_pod_name().filter(|_| reload);
+                        pods::sort_pods(&mut pods, self.pod_sort, self.pod_sort_descending);
+                        self.pods = pods;
+                        if reload {
+                            self.marked.retain(|n| self.pods.iter().any(|p| &p.name == n));
+                        } else {
+                            self.marked.clear();
+                            self.filter.clear();
+                        }
+                        self.watch.changes.clear();
+                        self.selected_node = Some(node);
+                        self.state = AppState::NodePodList;
+                        self.select_name(selected);
+                        self.load_pod_metrics(None);
+                    }
+                    Err(e) => self.show_message(format!("Error loading pods: {}", e)),
+                }
+            }
+            JobResult::Secrets(origin, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(secrets) => {
+                        let selected = self.selected_secret().map(|s| s.name.clone());
+                        self.secrets = secrets;
+                        let index = selected
+                            .and_then(|name| self.secrets.iter().position(|s| s.name == name))
+                            .unwrap_or(0);
+                        self.secret_list_state.select(Some(index));
+                        self.state = AppState::SecretList;
+                    }
+                    Err(e) => self.show_message(format!("Error loading secrets: {}", e)),
+                }
+            }
+            JobResult::Copied(key, result) => {
+                self.status_message = Some(match result {
+                    Ok(()) => format!("Copied {} to the clipboard", key),
+                    Err(e) => format!("Error copying {}: {}", key, e),
+                });
+            }
+            JobResult::PodMetrics(namespace, result) => match result {
+                Ok(usage) => {
+                    let scope = namespace.map(|ns| format!("{}/", ns)).unwrap_or_default();
+                    self.metrics.pods.record(usage, &scope);
+                    self.metrics.reset();
+                }
+                Err(e) => self.metrics_failed(e),
+            },
+            JobResult::NodeMetrics(result) => match result {
+                Ok(usage) => {
+                    self.metrics.nodes.record(usage, "");
+                    self.metrics.reset();
+                }
+                Err(e) => self.metrics_failed(e),
+            },
+            JobResult::RolloutHistory(result) => {
+                if self.state != AppState::DeploymentList {
+                    return;
+                }
+                match result {
+                    Ok(output) => {
+                        self.revisions = workloads::parse_rollout_history(&output);
+                        self.revision_list_state.select(Some(0));
+                        self.state = AppState::RevisionSelection;
+                    }
+                    Err(e) => self.show_message(format!("Error loading rollout history: {}", e)),
+                }
+            }
+            JobResult::RolloutStatus(status, deployments) => {
+                let Some(rollout) = self.rollout.as_mut() else {
+                    return;
+                };
+                rollout.polling = false;
+                match status {
+                    Ok(output) => rollout.record(&output),
+                    Err(e) => rollout.record(&format!("Error: {}", e.trim())),
+                }
+                if let Ok(deployments) = deployments {
+                    rollout.progress = deployments
+                        .into_iter()
+                        .find(|d| d.name == rollout.deployment);
+                }
+            }
+            JobResult::Describe(origin, kind, name, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(output) => {
+                        self.describe = Some(DescribeView::new(&kind, &name, &output));
+                        self.detail_return = origin;
+                        self.state = AppState::Describe;
+                    }
+                    Err(e) => self.show_message(format!("Error describing {}: {}", name, e)),
+                }
+            }
+            JobResult::Manifest(origin, kind, name, result) => {
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(yaml) => {
+                        self.manifest = Some(ManifestView::new(&kind, &name, &yaml));
+                        self.detail_return = origin;
+                        self.state = AppState::Manifest;
+                    }
+                    Err(e) => self.show_message(format!("Error fetching YAML for {}: {}", name, e)),
+                }
+            }
+            JobResult::Output(output) => {
+                self.output = output;
+            }
+            JobResult::EditDiff(origin, mut pending, result) => {
+                // A stale diff is dropped along with its temp file.
+                if self.state != origin {
+                    return;
+                }
+                match result {
+                    Ok(diff) if diff.trim().is_empty() => {
+                        self.status_message = Some(format!(
+                            "No differences from the live {}/{}",
+                            pending.kind, pending.name
+                        ));
+                    }
+                    Ok(diff) => {
+                        pending.diff = diff.lines().map(|l| l.to_string()).collect();
+                        self.pending_edit = Some(pending);
+                        self.state = AppState::EditConfirm;
+                    }
+                    Err(e) => {
+                        let target = format!("{}/{}", pending.kind, pending.name);
+                        let path = pending.keep();
+                        self.show_message(format!(
+                            "Error editing {}: {} (edits kept in {})",
+                            target,
+                            e.trim(),
+                            path.display()
+                        ));
+                    }
+                }
+            }
+            JobResult::Finished(origin, result) => {
+                if self.state != origin {
+                    return;
+                }
+                self.output = result.unwrap_or_else(|e| e);
+                self.state = AppState::ShowOutput;
+            }
+        }
+    }
+
+    /// Asks for the ports to forward to `target`, a `kind/name` reference.
+    pub fn open_port_forward(&mut self, namespace: String, target: &str) {
+        self.pending_forward = Some(PendingForward {
+            namespace,
+            target: target.to_string(),
+            return_to: self.state,
+        });
+        self.port_forward_input.clear();
+        self.state = AppState::PortForwardInput;
+    }
+
+    pub fn cancel_port_forward(&mut self) {
+        if let Some(pending) = self.pending_forward.take() {
+            self.state = pending.return_to;
+        }
+    }
+
+    /// Starts `kubectl port-forward` with the entered ports and returns to
+    /// the list the target was picked from.
+    pub fn start_port_forward(&mut self) {
+        let (local, remote) = match portforward::parse_ports(&self.port_forward_input) {
+            Ok(ports) => ports,
+            Err(e) => {
+                self.status_message = Some(e);
+                return;
+            }
+        };
+        if let Some(existing) = self
+            .port_forwards
+            .iter()
+            .find(|f| f.local == local && f.is_running())
+        {
+            self.status_message = Some(format!(
+                "Local port {} is already forwarded to {}",
+                local, existing.target
+            ));
+            return;
+        }
+        let Some(pending) = self.pending_forward.take() else {
+            return;
+        };
+        self.state = pending.return_to;
+
+        let args = PortForward::args(&pending.namespace, &pending.target, local, remote);
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+        match self.kube.spawn(&args) {
+            Ok(child) => {
+                self.port_forwards.push(PortForward::new(
+                    &pending.namespace,
+                    &pending.target,
+                    local,
+                    remote,
+                    child,
+                ));
+                self.status_message = Some(format!(
+                    "Forwarding localhost:{} to {}:{}",
+                    local, pending.target, remote
+                ));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Error starting port forward: {}", e.trim()))
+            }
+        }
+    }
+
+    pub fn load_nodes(&mut self) {
+        let kube = self.kube.clone();
+        let origin = self.state;
+        self.worker.spawn("Loading nodes", move || {
+            JobResult::Nodes(origin, kube.list_nodes())
+        });
+    }
+
+    /// Fetches pod usage in `namespace`, or all namespaces if None.
+    pub fn load_pod_metrics(&mut self, namespace: Option<String>) {
+        if !self.metrics.due(Instant::now()) {
+            return;
+        }
+        let kube = self.kube.clone();
+        self.worker.spawn_background("Loading metrics", move || {
+            let result = kube.top_pods(namespace.as_deref());
+            JobResult::PodMetrics(namespace, result)
+        });
+    }
+
+    pub fn load_node_metrics(&mut self) {
+        if !self.metrics.due(Instant::now()) {
+            return;
+        }
+        let kube = self.kube.clone();
+        self.worker.spawn_background("Loading node metrics", move || {
+            JobResult::NodeMetrics(kube.top_nodes())
+        });
+    }
+
+    /// Notes once that metrics are unavailable, then retries with a growing
+    /// delay instead of failing every refresh.
+    fn metrics_failed(&mut self, error: String) {
+        if self.metrics.failed(Instant::now()) {
+            self.status_message = Some(format!("Usage columns unavailable: {}", error));
+        }
+    }
+
+    /// Current usage of a pod in the list on screen.
+    pub fn pod_usage(&self, pod: &Pod) -> Option<Usage> {
+        self.metrics.pods.latest(&pod.key())
+    }
+
+    pub fn pod_sparkline(&self, pod: &Pod) -> String {
+        self.metrics.pods.cpu_sparkline(&pod.key())
+    }
+
+    pub fn selected_node_name(&self) -> Option<String> {
+        let selected = self.node_list_state.selected().unwrap_or(0);
+        self.nodes.get(selected).map(|n| n.name.clone())
+    }
+
+    /// Cordons the highlighted node, or uncordons it with `cordon` false.
+    pub fn cordon_selected(&mut self, cordon: bool) {
+        if let Some(node) = self.selected_node_name() {
+            let (verb, label) = if cordon {
+                ("cordon", "Cordoning")
+            } else {
+                ("uncordon", "Uncordoning")
+            };
+            self.run_action(
+                &format!("{} {}", label, node),
+                vec![verb.to_string(), node],
+            );
+        }
+    }
+
+    /// Lists the pods scheduled on the highlighted node, or reloads those of
+    /// the node on screen.
+    pub fn load_node_pods(&mut self) {
+        let node = match self.state {
+            AppState::NodePodList => self.selected_node.clone(),
+            _ => self.selected_node_name(),
+        };
+        let Some(node) = node else {
+            return;
+        };
+        let kube = self.kube.clone();
+        let origin = self.state;
+        self.worker.spawn("Loading pods", move || {
+            let result = kube.list_node_pods(&node);
+            JobResult::NodePods(origin, node, result)
+        });
+    }
+
+    /// The namespace of `pod` in the pod list. Only the pods of a node span
+    /// namespaces; the other lists hold pods of the current one.
+    pub fn pod_namespace(&self, pod: &str) -> String {
+        self.pods
+            .iter()
+            .find(|p| p.name == pod && !p.namespace.is_empty())
+            .map(|p| p.namespace.clone())
+            .unwrap_or_else(|| self.current_namespace())
+    }
+
+    /// `args` run in the namespace of `pod`.
+    fn pod_args(&self, args: &[&str], pod: &str) -> Vec<String> {
+        let mut args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        args.extend(["-n".to_string(), self.pod_namespace(pod)]);
+        args
+    }
+
+    /// Describes `pods` in one view, with one `kubectl describe` per namespace
+    /// they live in.
+    pub fn describe_pods(&mut self, pods: &[String]) {
+        let mut by_namespace: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for pod in pods {
+            by_namespace.entry(self.pod_namespace(pod)).or_default().push(pod);
+        }
+        let commands: Vec<Vec<String>> = by_namespace
+            .into_iter()
+            .map(|(namespace, names)| {
+                let mut args = vec!["describe", "pod"];
+                args.extend(names);
+                args.extend(["-n", &namespace]);
+                args.iter().map(|a| a.to_string()).collect()
+            })
+            .collect();
+        let kube = self.kube.clone();
+        let origin = self.state;
+        let name = pods.join(", ");
+        self.worker.spawn("Describing", move || {
+            let output: Result<Vec<String>, String> = commands
+                .iter()
+                .map(|args| {
+                    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+                    kube.run(&args)
+                })
+                .collect();
+            JobResult::Describe(origin, "pod".to_string(), name, output.map(|o| o.join("\n")))
+        });
+    }
+
+    pub fn view_pod_manifest(&mut self, pod: &str) {
+        let args = self.pod_args(&["get", "pod", pod, "-o", "yaml"], pod);
+        self.spawn_resource_job("Fetching YAML", args, "pod", pod, JobResult::Manifest);
+    }
+
+    pub fn open_drain_options(&mut self) {
+        if let Some(node) = self.selected_node_name() {
+            self.pending_drain = Some(PendingDrain::new(&node));
+            self.state = AppState::DrainOptions;
+        }
+    }
+
+    pub fn cancel_drain_options(&mut self) {
+        self.pending_drain = None;
+        self.state = AppState::NodeList;
+    }
+
+    /// Starts `kubectl drain` with the chosen options and follows its
+    /// progress.
+    pub fn start_drain(&mut self) {
+        let Some(pending) = self.pending_drain.take() else {
+            return;
+        };
+        let args = pending.args();
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+        match self.kube.spawn(&args) {
+            Ok(child) => {
+                self.drain = Some(Drain::new(&pending.node, child));
+                self.state = AppState::DrainProgress;
+            }
+            Err(e) => {
+                self.state = AppState::NodeList;
+                self.status_message = Some(format!("Error starting drain: {}", e.trim()));
+            }
+        }
+    }
+
+    pub fn poll_drain(&mut self) {
+        if let Some(drain) = self.drain.as_mut() {
+            drain.poll();
+        }
+    }
+
+    /// Leaves the drain view, stopping kubectl if it is still draining.
+    pub fn close_drain(&mut self) {
+        if let Some(mut drain) = self.drain.take() {
+            drain.stop();
+            self.status_message = Some(format!("{} {}", drain.status.label(), drain.node));
+        }
+        self.state = AppState::NodeList;
+        self.load_nodes();
+    }
+
+    pub fn load_secrets(&mut self) {
+        let kube = self.kube.clone();
+        let origin = self.state;
         let namespace = self.current_namespace();
-        let output = Command::new("kubectl")
-            .args(&["exec", "-it", "-n", &namespace, pod, "--", "bash"])
-            .output()?;
+        self.worker.spawn("Loading secrets", move || {
+            JobResult::Secrets(origin, kube.list_secrets(&namespace))
+        });
+    }
+
+    pub fn selected_secret(&self) -> Option<&Secret> {
+        let selected = self.secret_list_state.selected().unwrap_or(0);
+        self.secrets.get(selected)
+    }
+
+    /// Shows the keys of the highlighted secret, all of them masked.
+    pub fn open_secret(&mut self) {
+        if let Some(secret) = self.selected_secret().cloned() {
+            self.secret_view = Some(SecretView::new(secret));
+            self.state = AppState::SecretDetail;
+        }
+    }
+
+    pub fn close_secret(&mut self) {
+        self.secret_view = None;
+        self.state = AppState::SecretList;
+    }
+
+    /// Copies the decoded value of the selected key, revealed or not.
+    pub fn copy_secret_value(&mut self) {
+        let Some(view) = &self.secret_view else {
+            return;
+        };
+        let Some(key) = view.selected_key() else {
+            return;
+        };
+        // Binary values are copied as they are, not as the text shown for them.
+        let Some(value) = view.secret.decoded(key) else {
+            self.status_message = Some(format!("Cannot copy {}: not valid base64", key));
+            return;
+        };
+        let key = key.to_string();
+        let Some(command) = self.clipboard.clone() else {
+            // OSC 52 is written to the terminal, which only this thread draws on.
+            let result = clipboard::copy(None, &value).map_err(|e| e.to_string());
+            self.apply_job_result(JobResult::Copied(key, result));
+            return;
+        };
+        // Clipboard tools such as xclip can stay around for a while.
+        self.worker.spawn("Copying", move || {
+            let result = clipboard::copy(Some(&command), &value).map_err(|e| e.to_string());
+            JobResult::Copied(key, result)
+        });
+    }
+
+    /// Picks up status changes of every forward. Called from the event loop
+    /// on every tick.
+    pub fn poll_port_forwards(&mut self) {
+        for forward in &mut self.port_forwards {
+            forward.poll();
+        }
+    }
+
+    pub fn open_port_forwards(&mut self) {
+        if self.port_forward_list_state.selected().is_none() {
+            self.port_forward_list_state.select(Some(0));
+        }
+        self.state = AppState::PortForwardList;
+    }
 
-        if output.status.success() {
-            self.output = String::from_utf8_lossy(&output.stdout).to_string();
+    /// Kills the selected forward and removes it from the list.
+    pub fn stop_selected_port_forward(&mut self) {
+        let selected = self.port_forward_list_state.selected().unwrap_or(0);
+        if selected >= self.port_forwards.len() {
+            return;
+        }
+        let forward = self.port_forwards.remove(selected);
+        self.status_message = Some(format!(
+            "Stopped forwarding localhost:{} to {}",
+            forward.local, forward.target
+        ));
+        self.port_forward_list_state
+            .select(Some(selected.min(self.port_forwards.len().saturating_sub(1))));
+    }
+
+    pub fn show_message(&mut self, message: String) {
+        self.message = message;
+        self.state = AppState::Message;
+    }
+
+    fn open_logs_or_pick_container(&mut self, pod: &str) {
+        if self.containers.len() > 1 {
+            self.state = AppState::LogContainerSelection;
+            return;
+        }
+
+        let container = self.selected_container_name();
+        if let Err(e) = self.open_logs(pod, container.as_deref()) {
+            self.show_message(format!("Error streaming logs: {}", e));
+        }
+    }
+
+    fn pick_exec_container(&mut self) {
+        if self.containers.len() > 1 {
+            self.state = AppState::ExecContainerSelection;
         } else {
-            self.output = String::from_utf8_lossy(&output.stderr).to_string();
+            self.selected_container = self.selected_container_name();
+            self.open_shell_selection();
+        }
+    }
+
+    /// Ephemeral containers cannot be targeted, so only regular ones are offered.
+    fn pick_debug_target(&mut self) {
+        self.containers.retain(|c| !c.ephemeral);
+        if self.containers.len() > 1 {
+            self.state = AppState::DebugContainerSelection;
+        } else {
+            self.selected_container = self.selected_container_name();
+            self.open_debug_image_selection();
+        }
+    }
+
+    pub fn open_debug_image_selection(&mut self) {
+        self.debug_image_list_state.select(Some(0));
+        self.state = AppState::DebugImageSelection;
+    }
+
+    /// Attaches an ephemeral container running the highlighted image to the
+    /// selected pod.
+    pub fn debug_pod(&mut self) {
+        let selected = self.debug_image_list_state.selected().unwrap_or(0);
+        let (Some(pod), Some(image)) = (self.selected_pod.clone(), self.debug_images.get(selected))
+        else {
+            return;
+        };
+
+        let args = debug::ephemeral_args(
+            &self.current_namespace(),
+            &pod,
+            self.selected_container.as_deref(),
+            image,
+        );
+        self.interactive = Some(Interactive::Debug {
+            pod,
+            args,
+            return_to: AppState::DebugPodSelection,
+        });
+    }
+
+    fn open_copy_form(&mut self, pod: &str) {
+        let containers = self
+            .containers
+            .iter()
+            .filter(|c| !c.ephemeral)
+            .map(|c| c.name.clone())
+            .collect();
+        self.copy_form = CopyPodForm::new(pod, containers);
+        self.state = AppState::CopyPodForm;
+    }
+
+    pub fn open_shell_selection(&mut self) {
+        self.shell_list_state.select(Some(0));
+        self.exec_command.clear();
+        self.state = AppState::ShellSelection;
+    }
+
+    /// Text input screens receive every key, including `q`.
+    pub fn is_text_input(&self) -> bool {
+        match self.state {
+            AppState::CopyPodForm
+            | AppState::ExecCommandInput
+            | AppState::ScaleInput
+            | AppState::PortForwardInput => true,
+            AppState::Manifest => self.manifest.as_ref().is_some_and(|m| m.searching),
+            AppState::ConfirmDelete => self
+                .pending_delete
+                .as_ref()
+                .is_some_and(|p| p.confirm_text.is_some()),
+            _ => self.is_filtering(),
         }
-        self.state = AppState::ShowOutput;
-        Ok(())
     }
 }
@@ -0,0 +1,88 @@
+use crate::app::AppState;
+use crate::kube::KubeBackend;
+
+/// Per-item outcome of an action run over several marked items.
+pub type ItemResults = Vec<(String, Result<String, String>)>;
+
+pub struct BulkSummary {
+    pub label: String,
+    pub results: ItemResults,
+    pub return_to: AppState,
+}
+
+impl BulkSummary {
+    pub fn failed(&self) -> usize {
+        self.results.iter().filter(|(_, r)| r.is_err()).count()
+    }
+
+    pub fn title(&self) -> String {
+        format!(
+            "{}: {} succeeded, {} failed",
+            self.label,
+            self.results.len() - self.failed(),
+            self.failed()
+        )
+    }
+}
+
+/// Runs one kubectl command per item and collects every outcome.
+pub fn run_each(kube: &dyn KubeBackend, items: &[(String, Vec<String>)]) -> ItemResults {
+    items
+        .iter()
+        .map(|(item, args)| {
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            let result = kube.run(&args).map(|out| out.trim().to_string());
+            (item.clone(), result.map_err(|e| e.trim().to_string()))
+        })
+        .collect()
+}
+
+/// The `kind/name` that `kubectl rollout restart` needs to replace a pod
+/// owned by `owner`. ReplicaSets are followed up to their Deployment.
+pub fn restart_target(
+    kube: &dyn KubeBackend,
+    namespace: &str,
+    owner: Option<&(String, String)>,
+) -> Result<String, String> {
+    match owner {
+        Some((kind, name)) if kind == "ReplicaSet" => {
+            let owner = kube.run(&[
+                "get",
+                "replicaset",
+                name,
+                "-n",
+                namespace,
+                "-o",
+                "jsonpath={.metadata.ownerReferences[0].kind}/{.metadata.ownerReferences[0].name}",
+            ])?;
+            match owner.trim().split_once('/') {
+                Some(("Deployment", deployment)) if !deployment.is_empty() => {
+                    Ok(format!("deployment/{}", deployment))
+                }
+                _ => Err(format!("ReplicaSet {} is not owned by a Deployment", name)),
+            }
+        }
+        Some((kind, name)) if kind == "StatefulSet" || kind == "DaemonSet" => {
+            Ok(format!("{}/{}", kind.to_lowercase(), name))
+        }
+        Some((kind, name)) => Err(format!("{} {} cannot be restarted", kind, name)),
+        None => Err("Pod has no owner to restart".to_string()),
+    }
+}
+
+/// Joins per-pod logs into one text with a foldable section per pod.
+pub fn combine_logs(results: &ItemResults) -> String {
+    let mut text = String::new();
+    for (pod, result) in results {
+        text.push_str(&format!("pod/{}:\n", pod));
+        let body = match result {
+            Ok(logs) if logs.trim().is_empty() => "(no output)".to_string(),
+            Ok(logs) => logs.clone(),
+            Err(e) => format!("error: {}", e),
+        };
+        for line in body.lines() {
+            text.push_str(&format!("  {}\n", line));
+        }
+    }
+    text
+}
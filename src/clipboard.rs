@@ -0,0 +1,64 @@
+use std::{
+    env,
+    error::Error,
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use crate::secrets::encode_base64;
+
+/// Clipboard tools tried in order when KUBETUI_CLIPBOARD is unset.
+const TOOLS: [&str; 4] = [
+    "pbcopy",
+    "wl-copy",
+    "xclip -selection clipboard",
+    "xsel --clipboard --input",
+];
+
+/// `$KUBETUI_CLIPBOARD`, then the first clipboard tool found on `PATH`.
+pub fn clipboard_command() -> Option<String> {
+    env::var("KUBETUI_CLIPBOARD")
+        .ok()
+        .filter(|c| !c.trim().is_empty())
+        .or_else(|| {
+            TOOLS
+                .iter()
+                .find(|tool| tool.split_whitespace().next().is_some_and(on_path))
+                .map(|tool| tool.to_string())
+        })
+}
+
+fn on_path(program: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// Pipes `bytes` to `command`, which may carry arguments, and waits for it
+/// to exit. Without a command the terminal is asked to copy them with an
+/// OSC 52 escape sequence.
+pub fn copy(command: Option<&str>, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+    let Some(command) = command else {
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b]52;c;{}\x07", encode_base64(bytes))?;
+        stdout.flush()?;
+        return Ok(());
+    };
+
+    let mut parts = command.split_whitespace();
+    let program = parts.next().ok_or("No clipboard command configured")?;
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Closing stdin tells the tool the text is complete.
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(bytes)?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", command, status).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CopyField {
+    NewName,
+    Container,
+    Image,
+    SetImage,
+    ShareProcesses,
+    SameNode,
+    Attach,
+}
+
+impl CopyField {
+    pub const ALL: [CopyField; 7] = [
+        CopyField::NewName,
+        CopyField::Container,
+        CopyField::Image,
+        CopyField::SetImage,
+        CopyField::ShareProcesses,
+        CopyField::SameNode,
+        CopyField::Attach,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CopyField::NewName => "New pod name",
+            CopyField::Container => "Target container",
+            CopyField::Image => "Image",
+            CopyField::SetImage => "Set image",
+            CopyField::ShareProcesses => "Share processes",
+            CopyField::SameNode => "Same node",
+            CopyField::Attach => "Attach afterwards",
+        }
+    }
+
+    pub fn is_text(self) -> bool {
+        matches!(
+            self,
+            CopyField::NewName | CopyField::Image | CopyField::SetImage
+        )
+    }
+}
+
+/// The options of `kubectl debug --copy-to`, edited one field at a time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CopyPodForm {
+    pub pod: String,
+    pub containers: Vec<String>,
+    pub focus: usize,
+
+    pub new_name: String,
+    pub container: usize,
+    /// Image for the target container; empty keeps the pod's image.
+    pub image: String,
+    /// Extra `--set-image` value such as `app=nginx:debug` or `*=busybox`.
+    pub set_image: String,
+    pub share_processes: bool,
+    pub same_node: bool,
+    pub attach: bool,
+}
+
+impl CopyPodForm {
+    pub fn new(pod: &str, containers: Vec<String>) -> Self {
+        CopyPodForm {
+            pod: pod.to_string(),
+            containers,
+            new_name: format!("{}-debug", pod),
+            share_processes: true,
+            attach: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn focused(&self) -> CopyField {
+        CopyField::ALL[self.focus]
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % CopyField::ALL.len();
+    }
+
+    pub fn focus_previous(&mut self) {
+        self.focus = self.focus.checked_sub(1).unwrap_or(CopyField::ALL.len() - 1);
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.focused() {
+            CopyField::NewName => Some(&mut self.new_name),
+            CopyField::Image => Some(&mut self.image),
+            CopyField::SetImage => Some(&mut self.set_image),
+            _ => None,
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        if let Some(text) = self.text_mut() {
+            text.push(c);
+        } else if c == ' ' {
+            self.toggle(true);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        if let Some(text) = self.text_mut() {
+            text.pop();
+        }
+    }
+
+    /// Flips a checkbox or cycles the target container in either direction.
+    pub fn toggle(&mut self, forward: bool) {
+        match self.focused() {
+            CopyField::Container if !self.containers.is_empty() => {
+                let len = self.containers.len();
+                self.container = if forward {
+                    (self.container + 1) % len
+                } else {
+                    (self.container + len - 1) % len
+                };
+            }
+            CopyField::ShareProcesses => self.share_processes = !self.share_processes,
+            CopyField::SameNode => self.same_node = !self.same_node,
+            CopyField::Attach => self.attach = !self.attach,
+            _ => {}
+        }
+    }
+
+    pub fn target_container(&self) -> Option<&str> {
+        self.containers.get(self.container).map(|c| c.as_str())
+    }
+
+    pub fn value(&self, field: CopyField) -> String {
+        let check = |on: bool| if on { "[x]" } else { "[ ]" }.to_string();
+        match field {
+            CopyField::NewName => self.new_name.clone(),
+            CopyField::Container => match self.target_container() {
+                Some(container) => format!("◀ {} ▶", container),
+                None => "<none>".to_string(),
+            },
+            CopyField::Image if self.image.is_empty() => "(keep)".to_string(),
+            CopyField::Image => self.image.clone(),
+            CopyField::SetImage => self.set_image.clone(),
+            CopyField::ShareProcesses => check(self.share_processes),
+            CopyField::SameNode => check(self.same_node),
+            CopyField::Attach => check(self.attach),
+        }
+    }
+
+    /// Arguments for `kubectl debug` that create the copy and, if requested,
+    /// attach to it.
+    pub fn args(&self, namespace: &str) -> Vec<String> {
+        let mut args = vec![
+            "debug".to_string(),
+            "-n".to_string(),
+            namespace.to_string(),
+            self.pod.clone(),
+            format!("--copy-to={}", self.new_name.trim()),
+        ];
+        if let Some(container) = self.target_container() {
+            args.push(format!("--container={}", container));
+        }
+        if !self.image.trim().is_empty() {
+            args.push(format!("--image={}", self.image.trim()));
+        }
+        if !self.set_image.trim().is_empty() {
+            args.push(format!("--set-image={}", self.set_image.trim()));
+        }
+        args.push(format!("--share-processes={}", self.share_processes));
+        if self.same_node {
+            args.push("--same-node".to_string());
+        }
+        if self.attach {
+            args.push("-it".to_string());
+        }
+        args
+    }
+}
+
+/// Images offered for ephemeral debug containers, unless overridden with a
+/// comma-separated `KUBETUI_DEBUG_IMAGES`.
+pub const DEFAULT_DEBUG_IMAGES: [&str; 4] = [
+    "busybox",
+    "nicolaka/netshoot",
+    "alpine",
+    "ubuntu",
+];
+
+pub fn debug_images() -> Vec<String> {
+    std::env::var("KUBETUI_DEBUG_IMAGES")
+        .ok()
+        .map(|value| parse_image_list(&value))
+        .filter(|images| !images.is_empty())
+        .unwrap_or_else(|| DEFAULT_DEBUG_IMAGES.iter().map(|i| i.to_string()).collect())
+}
+
+fn parse_image_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|i| i.trim())
+        .filter(|i| !i.is_empty())
+        .map(|i| i.to_string())
+        .collect()
+}
+
+/// Arguments for `kubectl debug` that add an ephemeral container running
+/// `image` to `pod` and attach to it.
+pub fn ephemeral_args(namespace: &str, pod: &str, target: Option<&str>, image: &str) -> Vec<String> {
+    let mut args = vec![
+        "debug".to_string(),
+        "-it".to_string(),
+        "-n".to_string(),
+        namespace.to_string(),
+        pod.to_string(),
+        format!("--image={}", image),
+    ];
+    if let Some(target) = target {
+        args.push(format!("--target={}", target));
+    }
+    args
+}
@@ -0,0 +1,165 @@
+use crate::app::AppState;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeleteField {
+    Confirm,
+    GracePeriod,
+    Force,
+}
+
+/// Grace periods offered in the delete dialog; `None` keeps the resource's own.
+pub const GRACE_PERIODS: [Option<u32>; 5] = [None, Some(0), Some(5), Some(30), Some(60)];
+
+/// A delete waiting for the user to confirm it in the delete dialog.
+pub struct PendingDelete {
+    pub kind: String,
+    pub names: Vec<String>,
+    pub namespaced: bool,
+    pub return_to: AppState,
+
+    /// Text that has to be typed before deleting, set in production contexts:
+    /// the resource name, or the context name when deleting several.
+    pub confirm_text: Option<String>,
+    pub typed: String,
+    pub focus: usize,
+    /// Index into `GRACE_PERIODS`.
+    pub grace_period: usize,
+    pub force: bool,
+}
+
+impl PendingDelete {
+    pub fn new(
+        kind: &str,
+        names: Vec<String>,
+        namespaced: bool,
+        return_to: AppState,
+        production_context: Option<&str>,
+    ) -> Self {
+        let confirm_text = production_context.map(|context| match names.as_slice() {
+            [name] => name.clone(),
+            _ => context.to_string(),
+        });
+        PendingDelete {
+            kind: kind.to_string(),
+            names,
+            namespaced,
+            return_to,
+            confirm_text,
+            typed: String::new(),
+            focus: 0,
+            grace_period: 0,
+            force: false,
+        }
+    }
+
+    pub fn fields(&self) -> Vec<DeleteField> {
+        let mut fields = Vec::new();
+        if self.confirm_text.is_some() {
+            fields.push(DeleteField::Confirm);
+        }
+        fields.extend([DeleteField::GracePeriod, DeleteField::Force]);
+        fields
+    }
+
+    pub fn focused(&self) -> DeleteField {
+        self.fields()[self.focus]
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % self.fields().len();
+    }
+
+    pub fn focus_previous(&mut self) {
+        let len = self.fields().len();
+        self.focus = (self.focus + len - 1) % len;
+    }
+
+    pub fn push(&mut self, c: char) {
+        if self.focused() == DeleteField::Confirm {
+            self.typed.push(c);
+        } else if c == ' ' {
+            self.toggle(true);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        if self.focused() == DeleteField::Confirm {
+            self.typed.pop();
+        }
+    }
+
+    /// Cycles the grace period or flips `--force`.
+    pub fn toggle(&mut self, forward: bool) {
+        match self.focused() {
+            DeleteField::GracePeriod => {
+                let len = GRACE_PERIODS.len();
+                self.grace_period = if forward {
+                    (self.grace_period + 1) % len
+                } else {
+                    (self.grace_period + len - 1) % len
+                };
+            }
+            DeleteField::Force => self.force = !self.force,
+            DeleteField::Confirm => {}
+        }
+    }
+
+    /// Whether the required text, if any, has been typed.
+    pub fn confirmed(&self) -> bool {
+        self.confirm_text
+            .as_ref()
+            .is_none_or(|text| self.typed.trim() == text)
+    }
+
+    /// `kind/name`, or a count when several resources are selected.
+    pub fn target(&self) -> String {
+        match self.names.as_slice() {
+            [name] => format!("{}/{}", self.kind, name),
+            names => format!("{} {} resources", names.len(), self.kind),
+        }
+    }
+
+    pub fn grace_display(&self) -> String {
+        if self.force {
+            return "0s (forced)".to_string();
+        }
+        match GRACE_PERIODS[self.grace_period] {
+            Some(seconds) => format!("{}s", seconds),
+            None => "default".to_string(),
+        }
+    }
+
+    /// Arguments for `kubectl delete name`, without the namespace.
+    pub fn args(&self, name: &str) -> Vec<String> {
+        let mut args = vec!["delete".to_string(), self.kind.clone(), name.to_string()];
+        if self.force {
+            // kubectl only skips graceful deletion with a zero grace period.
+            args.push("--grace-period=0".to_string());
+            args.push("--force".to_string());
+        } else if let Some(seconds) = GRACE_PERIODS[self.grace_period] {
+            args.push(format!("--grace-period={}", seconds));
+        }
+        args
+    }
+}
+
+/// Context name fragments that mark a context as production, from the
+/// comma-separated `KUBETUI_PRODUCTION_CONTEXTS` (default: `prod`).
+pub fn production_patterns() -> Vec<String> {
+    std::env::var("KUBETUI_PRODUCTION_CONTEXTS")
+        .ok()
+        .map(|value| {
+            value
+                .split(',')
+                .map(|p| p.trim().to_lowercase())
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|patterns| !patterns.is_empty())
+        .unwrap_or_else(|| vec!["prod".to_string()])
+}
+
+pub fn is_production(context: &str) -> bool {
+    let context = context.to_lowercase();
+    production_patterns().iter().any(|p| context.contains(p.as_str()))
+}
@@ -0,0 +1,169 @@
+/// A top-level block of `kubectl describe` output: an unindented heading such
+/// as `Labels:` or `Events:` followed by its indented body.
+pub struct Section {
+    pub start: usize,
+    pub end: usize,
+    pub folded: bool,
+}
+
+/// One line of the describe output as currently shown, after folding.
+pub struct Row<'a> {
+    pub line: usize,
+    pub text: &'a str,
+    /// Some if this line heads a foldable section.
+    pub section: Option<&'a Section>,
+}
+
+pub struct DescribeView {
+    /// What the output is of, "Describe" unless set otherwise.
+    pub heading: String,
+    pub kind: String,
+    pub name: String,
+
+    pub lines: Vec<String>,
+    pub sections: Vec<Section>,
+
+    /// Index of the highlighted row among the visible rows.
+    pub cursor: usize,
+    pub scroll: usize,
+    pub page_height: usize,
+}
+
+impl DescribeView {
+    pub fn new(kind: &str, name: &str, output: &str) -> Self {
+        let lines: Vec<String> = output.lines().map(|l| l.to_string()).collect();
+        let sections = parse_sections(&lines);
+        DescribeView {
+            heading: "Describe".to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            lines,
+            sections,
+            cursor: 0,
+            scroll: 0,
+            page_height: 0,
+        }
+    }
+
+    pub fn rows(&self) -> Vec<Row<'_>> {
+        let mut rows = Vec::new();
+        let mut index = 0;
+        while index < self.lines.len() {
+            let section = self.sections.iter().find(|s| s.start == index);
+            rows.push(Row {
+                line: index,
+                text: &self.lines[index],
+                section,
+            });
+            index = match section {
+                Some(section) if section.folded => section.end,
+                _ => index + 1,
+            };
+        }
+        rows
+    }
+
+    /// The section the highlighted row belongs to, if any.
+    fn current_section(&self) -> Option<usize> {
+        let line = self.rows().get(self.cursor)?.line;
+        self.sections
+            .iter()
+            .position(|s| s.start <= line && line < s.end)
+    }
+
+    /// Folds or unfolds the section under the cursor and moves the cursor to
+    /// its heading.
+    pub fn toggle_fold(&mut self) {
+        let Some(section) = self.current_section() else {
+            return;
+        };
+        self.sections[section].folded = !self.sections[section].folded;
+
+        let start = self.sections[section].start;
+        self.cursor = self
+            .rows()
+            .iter()
+            .position(|r| r.section.is_some_and(|s| s.start == start))
+            .unwrap_or(0);
+        self.keep_cursor_visible();
+    }
+
+    /// Folds every section, or unfolds them all if they already are.
+    pub fn toggle_fold_all(&mut self) {
+        let fold = self.sections.iter().any(|s| !s.folded);
+        for section in &mut self.sections {
+            section.folded = fold;
+        }
+        self.cursor = self.cursor.min(self.rows().len().saturating_sub(1));
+        self.keep_cursor_visible();
+    }
+
+    pub fn cursor_up(&mut self, amount: usize) {
+        self.cursor = self.cursor.saturating_sub(amount);
+        self.keep_cursor_visible();
+    }
+
+    pub fn cursor_down(&mut self, amount: usize) {
+        let last = self.rows().len().saturating_sub(1);
+        self.cursor = (self.cursor + amount).min(last);
+        self.keep_cursor_visible();
+    }
+
+    pub fn cursor_top(&mut self) {
+        self.cursor = 0;
+        self.keep_cursor_visible();
+    }
+
+    pub fn cursor_bottom(&mut self) {
+        self.cursor = self.rows().len().saturating_sub(1);
+        self.keep_cursor_visible();
+    }
+
+    /// Scrolls just enough to show the cursor. Does nothing until the first
+    /// render has set `page_height`.
+    pub fn keep_cursor_visible(&mut self) {
+        let page = self.page_height;
+        if page == 0 {
+            return;
+        }
+        let max_scroll = self.rows().len().saturating_sub(page);
+        self.scroll = self.scroll.min(max_scroll);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + page {
+            self.scroll = self.cursor + 1 - page;
+        }
+    }
+
+    pub fn title(&self) -> String {
+        format!("{} {}/{}", self.heading, self.kind, self.name)
+    }
+}
+
+/// Finds every unindented heading that has an indented body below it.
+fn parse_sections(lines: &[String]) -> Vec<Section> {
+    let is_body = |line: &String| line.starts_with(' ') || line.starts_with('\t');
+
+    let mut sections = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if is_body(&lines[index]) || lines[index].trim().is_empty() {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        index += 1;
+        while index < lines.len() && is_body(&lines[index]) {
+            index += 1;
+        }
+        if index - start > 1 {
+            sections.push(Section {
+                start,
+                end: index,
+                folded: false,
+            });
+        }
+    }
+    sections
+}
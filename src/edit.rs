@@ -0,0 +1,137 @@
+use std::{
+    collections::hash_map::RandomState,
+    env,
+    error::Error,
+    fs::{self, OpenOptions},
+    hash::{BuildHasher, Hasher},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+/// An edited manifest waiting for the user to confirm `kubectl apply`.
+pub struct PendingEdit {
+    pub kind: String,
+    pub name: String,
+    pub path: PathBuf,
+    /// Output of `kubectl diff` against the live object.
+    pub diff: Vec<String>,
+    pub scroll: usize,
+    pub page_height: usize,
+}
+
+impl PendingEdit {
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+
+    pub fn scroll_down(&mut self, amount: usize) {
+        let max = self.diff.len().saturating_sub(self.page_height.max(1));
+        self.scroll = (self.scroll + amount).min(max);
+    }
+
+    pub fn title(&self) -> String {
+        format!("Apply changes to {}/{}?", self.kind, self.name)
+    }
+
+    /// Leaves the temp file in place, so the user's changes survive, and
+    /// returns its path.
+    pub fn keep(mut self) -> PathBuf {
+        std::mem::take(&mut self.path)
+    }
+}
+
+impl Drop for PendingEdit {
+    fn drop(&mut self) {
+        if self.path.as_os_str().is_empty() {
+            return;
+        }
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// `$VISUAL`, then `$EDITOR`, then `vi`.
+pub fn editor_command() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Writes `yaml` to a new temp file that only the current user can read.
+/// Manifests may hold secrets, so the name is random and an existing file
+/// or symlink at the path is never written through.
+pub fn write_temp(kind: &str, name: &str, yaml: &str) -> io::Result<PathBuf> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    for _ in 0..8 {
+        let path = temp_path(kind, name);
+        match options.open(&path) {
+            Ok(mut file) => {
+                file.write_all(yaml.as_bytes())?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "Could not create a temporary file",
+    ))
+}
+
+fn temp_path(kind: &str, name: &str) -> PathBuf {
+    // RandomState is seeded randomly, which is all the randomness needed here.
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    env::temp_dir().join(format!(
+        "kubetui-{}-{}-{:016x}.yaml",
+        kind.replace('/', "_"),
+        name.replace('/', "_"),
+        hasher.finish()
+    ))
+}
+
+/// Opens `path` in `editor`, which may carry arguments such as `code --wait`.
+/// Returns whether the file changed.
+pub fn run_editor(editor: &str, path: &Path) -> Result<bool, Box<dyn Error>> {
+    let before = fs::read_to_string(path)?;
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("No editor configured")?;
+    let status = Command::new(program).args(parts).arg(path).status()?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", editor, status).into());
+    }
+
+    Ok(fs::read_to_string(path)? != before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_files_are_private_and_never_reused() {
+        let first = write_temp("secret", "db", "data: {}\n").unwrap();
+        let second = write_temp("secret", "db", "data: {}\n").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "data: {}\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_file(first).unwrap();
+        fs::remove_file(second).unwrap();
+    }
+}
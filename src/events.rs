@@ -0,0 +1,215 @@
+use std::{cmp::Reverse, error::Error};
+
+use serde_json::Value;
+
+use crate::pods::{format_age, parse_timestamp};
+use crate::workloads::parse_items;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub namespace: String,
+    /// Kind and name of the object the event is about.
+    pub kind: String,
+    pub object: String,
+    /// "Normal" or "Warning".
+    pub event_type: String,
+    pub reason: String,
+    pub message: String,
+    pub count: u64,
+    /// When the event was last seen, as seconds since the Unix epoch.
+    pub last_seen: Option<i64>,
+}
+
+impl Event {
+    fn from_json(item: &Value) -> Option<Event> {
+        let involved = &item["involvedObject"];
+        let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+        // Events from the events.k8s.io API only fill in eventTime.
+        let last_seen = ["lastTimestamp", "eventTime", "firstTimestamp"]
+            .iter()
+            .find_map(|key| item[key].as_str())
+            .or_else(|| item["metadata"]["creationTimestamp"].as_str())
+            .and_then(parse_timestamp);
+        Some(Event {
+            namespace: text(&item["metadata"]["namespace"]),
+            kind: involved["kind"].as_str()?.to_string(),
+            object: involved["name"].as_str()?.to_string(),
+            event_type: text(&item["type"]),
+            reason: text(&item["reason"]),
+            message: text(&item["message"]).trim().to_string(),
+            count: item["count"]
+                .as_u64()
+                .or_else(|| item["series"]["count"].as_u64())
+                .unwrap_or(1),
+            last_seen,
+        })
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.event_type == "Warning"
+    }
+
+    /// The involved object as kubectl prints it, e.g. `pod/api-1`.
+    pub fn object_display(&self) -> String {
+        format!("{}/{}", self.kind.to_lowercase(), self.object)
+    }
+
+    pub fn last_seen_display(&self) -> String {
+        match (self.last_seen.map(format_age), self.count) {
+            (None, _) => "<unknown>".to_string(),
+            (Some(age), 1) => age,
+            (Some(age), count) => format!("{} (x{})", age, count),
+        }
+    }
+}
+
+/// Parses the output of `kubectl get events -o json`, newest first.
+pub fn parse_events(json: &str) -> Result<Vec<Event>, Box<dyn Error>> {
+    let mut events = parse_items(json, Event::from_json)?;
+    sort_events(&mut events);
+    Ok(events)
+}
+
+pub fn sort_events(events: &mut [Event]) {
+    events.sort_by_key(|e| Reverse(e.last_seen));
+}
+
+/// The newest warnings as one line each, for the main menu preview.
+pub fn warning_summary(events: &[Event], limit: usize) -> Vec<String> {
+    events
+        .iter()
+        .filter(|e| e.is_warning())
+        .take(limit)
+        .map(|e| {
+            format!(
+                "{}  {}  {}: {}",
+                e.last_seen_display(),
+                e.object_display(),
+                e.reason,
+                e.message
+            )
+        })
+        .collect()
+}
+
+/// The object an event list is narrowed to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvolvedObject {
+    pub namespace: String,
+    pub kind: String,
+    pub name: String,
+}
+
+impl InvolvedObject {
+    pub fn of(event: &Event) -> Self {
+        InvolvedObject {
+            namespace: event.namespace.clone(),
+            kind: event.kind.clone(),
+            name: event.object.clone(),
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        event.namespace == self.namespace && event.kind == self.kind && event.object == self.name
+    }
+
+    pub fn display(&self) -> String {
+        format!("{}/{}", self.kind.to_lowercase(), self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENTS: &str = r#"{"apiVersion": "v1", "kind": "List", "items": [
+        {
+            "apiVersion": "v1",
+            "kind": "Event",
+            "metadata": {"name": "api-1.17b0c1", "namespace": "default",
+                         "creationTimestamp": "2024-01-31T11:00:00Z"},
+            "involvedObject": {"kind": "Pod", "name": "api-1", "namespace": "default",
+                               "fieldPath": "spec.containers{api}"},
+            "reason": "BackOff",
+            "message": "Back-off restarting failed container api in pod api-1\n",
+            "type": "Warning",
+            "count": 12,
+            "firstTimestamp": "2024-01-31T11:00:00Z",
+            "lastTimestamp": "2024-01-31T12:00:00Z"
+        },
+        {
+            "apiVersion": "v1",
+            "kind": "Event",
+            "metadata": {"name": "web.17b0c2", "namespace": "default",
+                         "creationTimestamp": "2024-01-31T12:05:00Z"},
+            "involvedObject": {"kind": "Deployment", "name": "web", "namespace": "default"},
+            "reason": "ScalingReplicaSet",
+            "message": "Scaled up replica set web-7d4 to 3",
+            "type": "Normal",
+            "eventTime": "2024-01-31T12:05:00.123456Z",
+            "firstTimestamp": null,
+            "lastTimestamp": null,
+            "series": {"count": 4, "lastObservedTime": "2024-01-31T12:05:00.123456Z"}
+        },
+        {
+            "metadata": {"name": "node-1.17b0c3", "namespace": "default"},
+            "involvedObject": {"kind": "Node", "name": "node-1"},
+            "reason": "NodeNotReady",
+            "type": "Warning"
+        },
+        {"metadata": {"name": "broken"}, "involvedObject": {}}
+    ]}"#;
+
+    #[test]
+    fn parses_events_newest_first() {
+        let events = parse_events(EVENTS).unwrap();
+        let objects: Vec<String> = events.iter().map(|e| e.object_display()).collect();
+        assert_eq!(objects, vec!["deployment/web", "pod/api-1", "node/node-1"]);
+
+        let web = &events[0];
+        assert_eq!(web.last_seen, Some(1_706_702_700));
+        assert_eq!(web.count, 4);
+        assert!(!web.is_warning());
+
+        let api = &events[1];
+        assert_eq!(api.namespace, "default");
+        assert_eq!(api.reason, "BackOff");
+        assert_eq!(api.message, "Back-off restarting failed container api in pod api-1");
+        assert_eq!(api.count, 12);
+        assert_eq!(api.last_seen, Some(1_706_702_400));
+        assert!(api.is_warning());
+
+        let node = &events[2];
+        assert_eq!((node.count, node.last_seen), (1, None));
+        assert_eq!(node.last_seen_display(), "<unknown>");
+
+        assert!(parse_events(r#"{"items": null}"#).is_err());
+    }
+
+    #[test]
+    fn summarises_warnings_only() {
+        let events = parse_events(EVENTS).unwrap();
+        let summary = warning_summary(&events, 5);
+        assert_eq!(summary.len(), 2);
+        assert!(summary[0].ends_with(
+            "(x12)  pod/api-1  BackOff: Back-off restarting failed container api in pod api-1"
+        ));
+        assert_eq!(summary[1], "<unknown>  node/node-1  NodeNotReady: ");
+        assert_eq!(warning_summary(&events, 1).len(), 1);
+    }
+
+    #[test]
+    fn involved_objects_match_by_namespace_kind_and_name() {
+        let events = parse_events(EVENTS).unwrap();
+        let pod = InvolvedObject::of(&events[1]);
+        assert_eq!(pod.display(), "pod/api-1");
+        let matching: Vec<bool> = events.iter().map(|e| pod.matches(e)).collect();
+        assert_eq!(matching, vec![false, true, false]);
+
+        let other_namespace = InvolvedObject {
+            namespace: "staging".to_string(),
+            ..pod
+        };
+        assert!(!other_namespace.matches(&events[1]));
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use tui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+use crate::app::AppState;
+
+/// The `/` filter of a list screen. It only narrows the list it was opened
+/// on, and is dropped when that list is left.
+#[derive(Default)]
+pub struct ListFilter {
+    pub query: String,
+    pub editing: bool,
+    pub list: Option<AppState>,
+}
+
+impl ListFilter {
+    pub fn open(&mut self, list: AppState) {
+        if self.list != Some(list) {
+            self.query.clear();
+        }
+        self.list = Some(list);
+        self.editing = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.query.clear();
+        self.editing = false;
+        self.list = None;
+    }
+
+    /// Whether the filter narrows `list`.
+    pub fn applies_to(&self, list: AppState) -> bool {
+        self.list == Some(list) && (self.editing || !self.query.is_empty())
+    }
+
+    /// The query as a label selector, if it looks like one.
+    pub fn selector(&self) -> Option<LabelSelector> {
+        self.query.contains('=').then(|| LabelSelector::parse(&self.query))
+    }
+
+    /// `title` with the query appended, like the manifest search.
+    pub fn title(&self, title: &str, list: AppState, shown: usize, total: usize) -> String {
+        if !self.applies_to(list) {
+            title.to_string()
+        } else if self.editing {
+            format!("{} /{}", title, self.query)
+        } else {
+            format!("{} /{} [{}/{}]", title, self.query, shown, total)
+        }
+    }
+}
+
+/// Character positions in `text` matching `query` in order, ignoring case,
+/// or None if `text` does not contain every character of `query`.
+pub fn fuzzy_positions(query: &str, text: &str) -> Option<Vec<usize>> {
+    let mut positions = Vec::new();
+    let mut chars = text.chars().enumerate();
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let (index, _) = chars.find(|(_, c)| c.to_lowercase().eq(wanted.to_lowercase()))?;
+        positions.push(index);
+    }
+    Some(positions)
+}
+
+/// `text` split into spans with the characters matching `query` highlighted.
+pub fn highlight(text: &str, query: &str) -> Vec<Span<'static>> {
+    let positions = fuzzy_positions(query, text).unwrap_or_default();
+    if positions.is_empty() {
+        return vec![Span::raw(text.to_string())];
+    }
+    let hit = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+    let mut spans: Vec<Span> = Vec::new();
+    let mut current = String::new();
+    let mut current_hit = false;
+    for (index, c) in text.chars().enumerate() {
+        let is_hit = positions.contains(&index);
+        if is_hit != current_hit && !current.is_empty() {
+            let style = if current_hit { hit } else { Style::default() };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_hit = is_hit;
+        current.push(c);
+    }
+    let style = if current_hit { hit } else { Style::default() };
+    spans.push(Span::styled(current, style));
+    spans
+}
+
+#[derive(Debug, PartialEq)]
+enum Requirement {
+    Equals(String, String),
+    NotEquals(String, String),
+    Exists(String),
+    NotExists(String),
+}
+
+/// An equality-based label selector such as `app=api,tier!=db`, matched the
+/// way `kubectl get -l` would.
+#[derive(Debug, PartialEq)]
+pub struct LabelSelector {
+    requirements: Vec<Requirement>,
+}
+
+impl LabelSelector {
+    pub fn parse(selector: &str) -> LabelSelector {
+        let requirements = selector
+            .split(',')
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(|term| {
+                if let Some((key, value)) = term.split_once("!=") {
+                    Requirement::NotEquals(key.trim().to_string(), value.trim().to_string())
+                } else if let Some((key, value)) =
+                    term.split_once("==").or_else(|| term.split_once('='))
+                {
+                    Requirement::Equals(key.trim().to_string(), value.trim().to_string())
+                } else if let Some(key) = term.strip_prefix('!') {
+                    Requirement::NotExists(key.trim().to_string())
+                } else {
+                    Requirement::Exists(term.to_string())
+                }
+            })
+            .collect();
+        LabelSelector { requirements }
+    }
+
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.requirements.iter().all(|requirement| match requirement {
+            Requirement::Equals(key, value) => labels.get(key) == Some(value),
+            Requirement::NotEquals(key, value) => labels.get(key) != Some(value),
+            Requirement::Exists(key) => labels.contains_key(key),
+            Requirement::NotExists(key) => !labels.contains_key(key),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn hits(spans: &[Span]) -> Vec<String> {
+        spans
+            .iter()
+            .filter(|s| s.style.fg == Some(Color::Yellow))
+            .map(|s| s.content.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy_positions_match_in_order_ignoring_case_and_spaces() {
+        assert_eq!(fuzzy_positions("apw", "api-web-1"), Some(vec![0, 1, 4]));
+        assert_eq!(fuzzy_positions("A W", "api-web-1"), Some(vec![0, 4]));
+        assert_eq!(fuzzy_positions("", "api-web-1"), Some(vec![]));
+        assert_eq!(fuzzy_positions("wa", "api-web-1"), None);
+        assert_eq!(fuzzy_positions("api-web-12", "api-web-1"), None);
+    }
+
+    #[test]
+    fn fuzzy_positions_count_characters_not_bytes() {
+        assert_eq!(fuzzy_positions("ü", "grün-api"), Some(vec![2]));
+        assert_eq!(fuzzy_positions("ÖA", "föö-api"), Some(vec![1, 4]));
+        assert_eq!(fuzzy_positions("日志", "日本-志"), Some(vec![0, 3]));
+    }
+
+    #[test]
+    fn highlight_groups_consecutive_hits() {
+        let spans = highlight("api-web-1", "apiw");
+        assert_eq!(hits(&spans), vec!["api", "w"]);
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "api-web-1");
+
+        assert_eq!(highlight("grün-api", "üa").len(), 5);
+        assert_eq!(hits(&highlight("grün-api", "üa")), vec!["ü", "a"]);
+        assert_eq!(highlight("api", "x"), vec![Span::raw("api")]);
+        assert_eq!(highlight("api", ""), vec![Span::raw("api")]);
+    }
+
+    #[test]
+    fn selectors_parse_equality_inequality_and_existence() {
+        let selector =
+            LabelSelector::parse(" app = api ,tier!=db,, track==stable, canary,!legacy ");
+        assert_eq!(
+            selector.requirements,
+            vec![
+                Requirement::Equals("app".to_string(), "api".to_string()),
+                Requirement::NotEquals("tier".to_string(), "db".to_string()),
+                Requirement::Equals("track".to_string(), "stable".to_string()),
+                Requirement::Exists("canary".to_string()),
+                Requirement::NotExists("legacy".to_string()),
+            ]
+        );
+        assert_eq!(LabelSelector::parse(" , ").requirements, vec![]);
+        assert_eq!(
+            LabelSelector::parse("app=").requirements,
+            vec![Requirement::Equals("app".to_string(), String::new())]
+        );
+    }
+
+    #[test]
+    fn selectors_match_like_kubectl() {
+        let api = labels(&[("app", "api"), ("tier", "web"), ("canary", "")]);
+        let db = labels(&[("app", "db"), ("tier", "db"), ("legacy", "true")]);
+
+        let matching = |selector: &str| -> Vec<bool> {
+            let selector = LabelSelector::parse(selector);
+            vec![selector.matches(&api), selector.matches(&db)]
+        };
+        assert_eq!(matching("app=api"), vec![true, false]);
+        assert_eq!(matching("tier!=db"), vec![true, false]);
+        // A missing label is not equal to anything.
+        assert_eq!(matching("track!=stable"), vec![true, true]);
+        assert_eq!(matching("canary"), vec![true, false]);
+        assert_eq!(matching("!legacy"), vec![true, false]);
+        assert_eq!(matching("app=api,tier=db"), vec![false, false]);
+        assert_eq!(matching("canary="), vec![true, false]);
+        assert_eq!(matching(""), vec![true, true]);
+    }
+
+    #[test]
+    fn filter_only_narrows_the_list_it_was_opened_on() {
+        let mut filter = ListFilter::default();
+        filter.open(AppState::ExecPodSelection);
+        assert!(filter.applies_to(AppState::ExecPodSelection));
+        assert!(!filter.applies_to(AppState::ResourceList));
+        assert_eq!(filter.title("Pods", AppState::ExecPodSelection, 1, 3), "Pods /");
+
+        filter.query = "tier=web".to_string();
+        filter.editing = false;
+        assert!(filter.selector().is_some());
+        assert_eq!(
+            filter.title("Pods", AppState::ExecPodSelection, 1, 3),
+            "Pods /tier=web [1/3]"
+        );
+
+        filter.open(AppState::ResourceList);
+        assert!(filter.query.is_empty());
+        assert!(filter.selector().is_none());
+    }
+}
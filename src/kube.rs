@@ -0,0 +1,325 @@
+use std::{
+    collections::HashMap,
+    process::{Child, Command, Stdio},
+};
+
+use crate::events::{self, Event};
+use crate::metrics::{self, Usage};
+use crate::nodes::{self, Node};
+use crate::pods::{self, Container, Pod};
+use crate::resources::{self, ApiResource};
+use crate::secrets::{self, Secret};
+use crate::workloads::{self, Deployment, Workload, WorkloadKind};
+
+pub mod fake;
+
+pub type KubeResult<T> = Result<T, String>;
+
+/// Everything kubetui needs from a cluster. Implementations must be shareable
+/// across the background worker threads.
+pub trait KubeBackend: Send + Sync {
+    fn current_context(&self) -> Option<String>;
+    fn list_contexts(&self) -> KubeResult<Vec<String>>;
+    fn switch_context(&self, context: &str) -> KubeResult<()>;
+    fn list_namespaces(&self) -> KubeResult<Vec<String>>;
+    fn list_pods(&self, namespace: &str) -> KubeResult<Vec<Pod>>;
+    fn list_containers(&self, namespace: &str, pod: &str) -> KubeResult<Vec<Container>>;
+    fn list_deployments(&self, namespace: &str) -> KubeResult<Vec<Deployment>>;
+    fn list_workloads(&self, kind: WorkloadKind, namespace: &str) -> KubeResult<Vec<Workload>>;
+    fn list_api_resources(&self) -> KubeResult<Vec<ApiResource>>;
+    fn list_secrets(&self, namespace: &str) -> KubeResult<Vec<Secret>>;
+
+    /// Lists nodes with the number of pods scheduled on each.
+    fn list_nodes(&self) -> KubeResult<Vec<Node>>;
+
+    /// Lists the pods scheduled on `node`, across all namespaces.
+    fn list_node_pods(&self, node: &str) -> KubeResult<Vec<Pod>>;
+
+    /// Current usage of pods by `namespace/name`, in `namespace` or all
+    /// namespaces if None. Fails when the cluster has no metrics API.
+    fn top_pods(&self, namespace: Option<&str>) -> KubeResult<HashMap<String, Usage>>;
+
+    /// Current usage of nodes by name.
+    fn top_nodes(&self) -> KubeResult<HashMap<String, Usage>>;
+
+    /// Lists events newest first, in `namespace` or all namespaces if None.
+    fn list_events(&self, namespace: Option<&str>) -> KubeResult<Vec<Event>>;
+
+    /// Lists instance names of `kind`, in `namespace` or cluster-wide if None.
+    fn list_resources(&self, kind: &str, namespace: Option<&str>) -> KubeResult<Vec<String>>;
+
+    /// Runs `command` inside `pod` attached to the current terminal and
+    /// returns its exit code.
+    fn exec(
+        &self,
+        namespace: &str,
+        pod: &str,
+        container: Option<&str>,
+        command: &[&str],
+    ) -> KubeResult<i32>;
+
+    /// Runs `command` inside `pod` without a terminal and returns its output.
+    fn exec_output(
+        &self,
+        namespace: &str,
+        pod: &str,
+        container: Option<&str>,
+        command: &[&str],
+    ) -> KubeResult<String>;
+
+    /// Runs `kubectl diff -f file` and returns the diff, which is empty if the
+    /// file matches the live objects.
+    fn diff(&self, file: &str) -> KubeResult<String>;
+
+    /// Runs kubectl attached to the current terminal and returns its exit code.
+    fn run_attached(&self, args: &[&str]) -> KubeResult<i32>;
+
+    /// Runs kubectl with arbitrary arguments, returning stdout on success and
+    /// stderr otherwise.
+    fn run(&self, args: &[&str]) -> KubeResult<String>;
+
+    /// Starts a long-running kubectl in the background with its stdout and
+    /// stderr piped. The caller owns the child and must reap it.
+    fn spawn(&self, args: &[&str]) -> KubeResult<Child>;
+}
+
+/// The default backend, shelling out to the `kubectl` binary on `PATH`.
+pub struct Kubectl;
+
+impl KubeBackend for Kubectl {
+    fn current_context(&self) -> Option<String> {
+        let ctx = self.run(&["config", "current-context"]).ok()?;
+        let ctx = ctx.trim();
+        if !ctx.is_empty() {
+            Some(ctx.to_string())
+        } else {
+            None
+        }
+    }
+
+    fn list_contexts(&self) -> KubeResult<Vec<String>> {
+        self.run(&["config", "get-contexts", "-o=name"])
+            .map(|out| out.lines().map(|s| s.to_string()).collect())
+            .map_err(|_| "Failed to load contexts".to_string())
+    }
+
+    fn switch_context(&self, context: &str) -> KubeResult<()> {
+        let status = Command::new("kubectl")
+            .args(["config", "use-context", context])
+            .status()
+            .map_err(|e| e.to_string())?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err("Failed to switch context".to_string())
+        }
+    }
+
+    fn list_namespaces(&self) -> KubeResult<Vec<String>> {
+        self.run(&["get", "namespaces", "-o=jsonpath='{.items[*].metadata.name}'"])
+            .map(|out| split_jsonpath(&out))
+            .map_err(|e| format!("Failed to get namespaces: {}", e))
+    }
+
+    fn list_pods(&self, namespace: &str) -> KubeResult<Vec<Pod>> {
+        self.run(&["get", "pods", "-n", namespace, "-o", "json"])
+            .map_err(|e| format!("Failed to get pods: {}", e))
+            .and_then(|out| pods::parse_pods(&out).map_err(|e| e.to_string()))
+    }
+
+    fn list_containers(&self, namespace: &str, pod: &str) -> KubeResult<Vec<Container>> {
+        self.run(&["get", "pod", pod, "-n", namespace, "-o", "json"])
+            .map_err(|e| format!("Failed to get containers: {}", e))
+            .and_then(|out| pods::parse_containers(&out).map_err(|e| e.to_string()))
+    }
+
+    fn list_deployments(&self, namespace: &str) -> KubeResult<Vec<Deployment>> {
+        self.run(&["get", "deployments", "-n", namespace, "-o", "json"])
+            .map_err(|e| format!("Failed to get deployments: {}", e))
+            .and_then(|out| workloads::parse_deployments(&out).map_err(|e| e.to_string()))
+    }
+
+    fn list_workloads(&self, kind: WorkloadKind, namespace: &str) -> KubeResult<Vec<Workload>> {
+        self.run(&["get", kind.resource(), "-n", namespace, "-o", "json"])
+            .map_err(|e| format!("Failed to get {}: {}", kind.title().to_lowercase(), e))
+            .and_then(|out| workloads::parse_workloads(kind, &out).map_err(|e| e.to_string()))
+    }
+
+    fn list_api_resources(&self) -> KubeResult<Vec<ApiResource>> {
+        let query = |namespaced: &str| {
+            self.run(&["api-resources", "--verbs=list", namespaced, "-o", "name"])
+                .map_err(|e| format!("Failed to get API resources: {}", e))
+        };
+        let namespaced = query("--namespaced=true")?;
+        let cluster = query("--namespaced=false")?;
+        Ok(resources::parse_api_resources(&namespaced, &cluster))
+    }
+
+    fn list_secrets(&self, namespace: &str) -> KubeResult<Vec<Secret>> {
+        self.run(&["get", "secrets", "-n", namespace, "-o", "json"])
+            .map_err(|e| format!("Failed to get secrets: {}", e))
+            .and_then(|out| secrets::parse_secrets(&out).map_err(|e| e.to_string()))
+    }
+
+    fn list_nodes(&self) -> KubeResult<Vec<Node>> {
+        let mut nodes = self
+            .run(&["get", "nodes", "-o", "json"])
+            .map_err(|e| format!("Failed to get nodes: {}", e))
+            .and_then(|out| nodes::parse_nodes(&out).map_err(|e| e.to_string()))?;
+        // Pod counts are a nicety; nodes are still listed without them.
+        if let Ok(pod_nodes) = self.run(&[
+            "get",
+            "pods",
+            "--all-namespaces",
+            "-o=jsonpath='{.items[*].spec.nodeName}'",
+        ]) {
+            nodes::count_pods(&mut nodes, &pod_nodes);
+        }
+        Ok(nodes)
+    }
+
+    fn list_node_pods(&self, node: &str) -> KubeResult<Vec<Pod>> {
+        let selector = format!("--field-selector=spec.nodeName={}", node);
+        self.run(&["get", "pods", "--all-namespaces", &selector, "-o", "json"])
+            .map_err(|e| format!("Failed to get pods: {}", e))
+            .and_then(|out| pods::parse_pods(&out).map_err(|e| e.to_string()))
+    }
+
+    fn top_pods(&self, namespace: Option<&str>) -> KubeResult<HashMap<String, Usage>> {
+        let mut args = vec!["top", "pods", "--no-headers"];
+        match namespace {
+            Some(namespace) => args.extend(["-n", namespace]),
+            None => args.push("--all-namespaces"),
+        }
+        self.run(&args)
+            .map(|out| metrics::parse_top_pods(&out, namespace))
+            .map_err(|e| format!("Failed to get pod metrics: {}", e.trim()))
+    }
+
+    fn top_nodes(&self) -> KubeResult<HashMap<String, Usage>> {
+        self.run(&["top", "nodes", "--no-headers"])
+            .map(|out| metrics::parse_top_nodes(&out))
+            .map_err(|e| format!("Failed to get node metrics: {}", e.trim()))
+    }
+
+    fn list_events(&self, namespace: Option<&str>) -> KubeResult<Vec<Event>> {
+        let mut args = vec!["get", "events", "-o", "json"];
+        match namespace {
+            Some(namespace) => args.extend(["-n", namespace]),
+            None => args.push("--all-namespaces"),
+        }
+        self.run(&args)
+            .map_err(|e| format!("Failed to get events: {}", e))
+            .and_then(|out| events::parse_events(&out).map_err(|e| e.to_string()))
+    }
+
+    fn list_resources(&self, kind: &str, namespace: Option<&str>) -> KubeResult<Vec<String>> {
+        let mut args = vec!["get", kind, "-o", "name"];
+        if let Some(namespace) = namespace {
+            args.extend(["-n", namespace]);
+        }
+        self.run(&args)
+            .map(|out| resources::parse_resource_names(&out))
+            .map_err(|e| format!("Failed to get {}: {}", kind, e))
+    }
+
+    fn exec(
+        &self,
+        namespace: &str,
+        pod: &str,
+        container: Option<&str>,
+        command: &[&str],
+    ) -> KubeResult<i32> {
+        let status = Command::new("kubectl")
+            .args(exec_args(true, namespace, pod, container, command))
+            .status()
+            .map_err(|e| e.to_string())?;
+        Ok(status.code().unwrap_or(1))
+    }
+
+    fn exec_output(
+        &self,
+        namespace: &str,
+        pod: &str,
+        container: Option<&str>,
+        command: &[&str],
+    ) -> KubeResult<String> {
+        let args = exec_args(false, namespace, pod, container, command);
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+        self.run(&args)
+    }
+
+    fn diff(&self, file: &str) -> KubeResult<String> {
+        let output = Command::new("kubectl")
+            .args(["diff", "-f", file])
+            .output()
+            .map_err(|e| e.to_string())?;
+
+        // kubectl diff exits with 1 when it found differences.
+        match output.status.code() {
+            Some(0) | Some(1) => Ok(String::from_utf8_lossy(&output.stdout).to_string()),
+            _ => Err(String::from_utf8_lossy(&output.stderr).to_string()),
+        }
+    }
+
+    fn run_attached(&self, args: &[&str]) -> KubeResult<i32> {
+        let status = Command::new("kubectl")
+            .args(args)
+            .status()
+            .map_err(|e| e.to_string())?;
+        Ok(status.code().unwrap_or(1))
+    }
+
+    fn run(&self, args: &[&str]) -> KubeResult<String> {
+        let output = Command::new("kubectl")
+            .args(args)
+            .output()
+            .map_err(|e| e.to_string())?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    fn spawn(&self, args: &[&str]) -> KubeResult<Child> {
+        Command::new("kubectl")
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start kubectl: {}", e))
+    }
+}
+
+fn exec_args(
+    interactive: bool,
+    namespace: &str,
+    pod: &str,
+    container: Option<&str>,
+    command: &[&str],
+) -> Vec<String> {
+    let mut args = vec!["exec"];
+    if interactive {
+        args.push("-it");
+    }
+    args.extend(["-n", namespace, pod]);
+    if let Some(container) = container {
+        args.extend(["-c", container]);
+    }
+    args.push("--");
+    args.extend(command);
+    args.into_iter().map(|a| a.to_string()).collect()
+}
+
+/// Splits the output of a quoted `{.items[*]...}` jsonpath query into names.
+fn split_jsonpath(output: &str) -> Vec<String> {
+    output
+        .trim_matches('\'')
+        .split_whitespace()
+        .map(|s| s.to_string())
+        .collect()
+}
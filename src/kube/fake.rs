@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    process::{Child, Command, Stdio},
+    sync::Mutex,
+};
+
+use super::{KubeBackend, KubeResult};
+use crate::events::{self, Event};
+use crate::metrics::Usage;
+use crate::nodes::Node;
+use crate::pods::{Container, Pod};
+use crate::resources::ApiResource;
+use crate::secrets::Secret;
+use crate::workloads::{Deployment, Workload, WorkloadKind};
+
+/// An in-memory cluster for tests. Every call is recorded in `calls`, and a
+/// `None` list makes the corresponding call fail.
+#[derive(Default)]
+pub struct FakeKube {
+    pub contexts: Option<Vec<String>>,
+    pub current_context: Mutex<Option<String>>,
+    pub namespaces: Option<Vec<String>>,
+    /// Pods keyed by namespace.
+    pub pods: HashMap<String, Vec<Pod>>,
+    /// Containers keyed by pod name.
+    pub containers: HashMap<String, Vec<Container>>,
+    /// Deployments keyed by namespace.
+    pub deployments: HashMap<String, Vec<Deployment>>,
+    /// Other workloads keyed by "kind namespace", e.g. "cronjob default".
+    pub workloads: HashMap<String, Vec<Workload>>,
+    /// Secrets keyed by namespace.
+    pub secrets: HashMap<String, Vec<Secret>>,
+    pub nodes: Vec<Node>,
+    /// Pod usage keyed by "namespace/name".
+    pub pod_metrics: Option<HashMap<String, Usage>>,
+    pub node_metrics: Option<HashMap<String, Usage>>,
+    /// Events keyed by namespace.
+    pub events: HashMap<String, Vec<Event>>,
+    pub api_resources: Vec<ApiResource>,
+    /// Resource names keyed by "kind" or "kind namespace".
+    pub resources: HashMap<String, Vec<String>>,
+    pub exec_exit_code: i32,
+    /// Commands that are not installed in any container.
+    pub missing_commands: Vec<String>,
+    /// Output returned by `run`, keyed by the space-joined arguments.
+    pub outputs: HashMap<String, String>,
+    /// Shell scripts standing in for `spawn`ed kubectl processes, keyed by
+    /// the space-joined arguments. Anything else sleeps until killed.
+    pub scripts: HashMap<String, String>,
+    pub calls: Mutex<Vec<String>>,
+}
+
+impl FakeKube {
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+impl KubeBackend for FakeKube {
+    fn current_context(&self) -> Option<String> {
+        self.current_context.lock().unwrap().clone()
+    }
+
+    fn list_contexts(&self) -> KubeResult<Vec<String>> {
+        self.record("list_contexts".to_string());
+        self.contexts
+            .clone()
+            .ok_or_else(|| "Failed to load contexts".to_string())
+    }
+
+    fn switch_context(&self, context: &str) -> KubeResult<()> {
+        self.record(format!("switch_context {}", context));
+        match &self.contexts {
+            Some(contexts) if contexts.iter().any(|c| c == context) => {
+                *self.current_context.lock().unwrap() = Some(context.to_string());
+                Ok(())
+            }
+            _ => Err("Failed to switch context".to_string()),
+        }
+    }
+
+    fn list_namespaces(&self) -> KubeResult<Vec<String>> {
+        self.record("list_namespaces".to_string());
+        self.namespaces
+            .clone()
+            .ok_or_else(|| "Failed to get namespaces: forbidden".to_string())
+    }
+
+    fn list_pods(&self, namespace: &str) -> KubeResult<Vec<Pod>> {
+        self.record(format!("list_pods {}", namespace));
+        self.pods
+            .get(namespace)
+            .cloned()
+            .ok_or_else(|| format!("Failed to get pods: namespace {} not found", namespace))
+    }
+
+    fn list_containers(&self, namespace: &str, pod: &str) -> KubeResult<Vec<Container>> {
+        self.record(format!("list_containers {} {}", namespace, pod));
+        self.containers
+            .get(pod)
+            .cloned()
+            .ok_or_else(|| format!("Failed to get containers: pod {} not found", pod))
+    }
+
+    fn list_deployments(&self, namespace: &str) -> KubeResult<Vec<Deployment>> {
+        self.record(format!("list_deployments {}", namespace));
+        Ok(self.deployments.get(namespace).cloned().unwrap_or_default())
+    }
+
+    fn list_workloads(&self, kind: WorkloadKind, namespace: &str) -> KubeResult<Vec<Workload>> {
+        let key = format!("{} {}", kind.resource(), namespace);
+        self.record(format!("list_workloads {}", key));
+        Ok(self.workloads.get(&key).cloned().unwrap_or_default())
+    }
+
+    fn list_api_resources(&self) -> KubeResult<Vec<ApiResource>> {
+        self.record("list_api_resources".to_string());
+        Ok(self.api_resources.clone())
+    }
+
+    fn list_secrets(&self, namespace: &str) -> KubeResult<Vec<Secret>> {
+        self.record(format!("list_secrets {}", namespace));
+        Ok(self.secrets.get(namespace).cloned().unwrap_or_default())
+    }
+
+    fn list_nodes(&self) -> KubeResult<Vec<Node>> {
+        self.record("list_nodes".to_string());
+        Ok(self.nodes.clone())
+    }
+
+    fn list_node_pods(&self, node: &str) -> KubeResult<Vec<Pod>> {
+        self.record(format!("list_node_pods {}", node));
+        let mut pods: Vec<Pod> = self
+            .pods
+            .values()
+            .flatten()
+            .filter(|p| p.node.as_deref() == Some(node))
+            .cloned()
+            .collect();
+        pods.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+        Ok(pods)
+    }
+
+    fn top_pods(&self, namespace: Option<&str>) -> KubeResult<HashMap<String, Usage>> {
+        self.record(format!("top_pods {}", namespace.unwrap_or("--all-namespaces")));
+        let metrics = self.pod_metrics.as_ref().ok_or_else(|| {
+            "Failed to get pod metrics: error: Metrics API not available".to_string()
+        })?;
+        let prefix = namespace.map(|n| format!("{}/", n)).unwrap_or_default();
+        Ok(metrics
+            .iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, usage)| (key.clone(), *usage))
+            .collect())
+    }
+
+    fn top_nodes(&self) -> KubeResult<HashMap<String, Usage>> {
+        self.record("top_nodes".to_string());
+        self.node_metrics.clone().ok_or_else(|| {
+            "Failed to get node metrics: error: Metrics API not available".to_string()
+        })
+    }
+
+    fn list_events(&self, namespace: Option<&str>) -> KubeResult<Vec<Event>> {
+        self.record(format!("list_events {}", namespace.unwrap_or("--all-namespaces")));
+        let mut events: Vec<Event> = match namespace {
+            Some(namespace) => self.events.get(namespace).cloned().unwrap_or_default(),
+            None => self.events.values().flatten().cloned().collect(),
+        };
+        events::sort_events(&mut events);
+        Ok(events)
+    }
+
+    fn list_resources(&self, kind: &str, namespace: Option<&str>) -> KubeResult<Vec<String>> {
+        let key = match namespace {
+            Some(namespace) => format!("{} {}", kind, namespace),
+            None => kind.to_string(),
+        };
+        self.record(format!("list_resources {}", key));
+        Ok(self.resources.get(&key).cloned().unwrap_or_default())
+    }
+
+    fn exec(
+        &self,
+        namespace: &str,
+        pod: &str,
+        container: Option<&str>,
+        command: &[&str],
+    ) -> KubeResult<i32> {
+        self.record(format!(
+            "exec {} {} {} {}",
+            namespace,
+            pod,
+            container.unwrap_or("-"),
+            command.join(" ")
+        ));
+        Ok(self.exec_exit_code)
+    }
+
+    fn exec_output(
+        &self,
+        namespace: &str,
+        pod: &str,
+        container: Option<&str>,
+        command: &[&str],
+    ) -> KubeResult<String> {
+        self.record(format!(
+            "exec_output {} {} {} {}",
+            namespace,
+            pod,
+            container.unwrap_or("-"),
+            command.join(" ")
+        ));
+        match command.first() {
+            Some(program) if self.missing_commands.iter().any(|m| m == program) => Err(format!(
+                "exec: \"{}\": executable file not found in $PATH",
+                program
+            )),
+            _ => Ok(String::new()),
+        }
+    }
+
+    /// Returns the `outputs` entry for "diff", so tests choose what changed.
+    fn diff(&self, file: &str) -> KubeResult<String> {
+        self.record(format!("diff {}", file));
+        Ok(self.outputs.get("diff").cloned().unwrap_or_default())
+    }
+
+    fn run_attached(&self, args: &[&str]) -> KubeResult<i32> {
+        self.record(format!("run_attached {}", args.join(" ")));
+        Ok(self.exec_exit_code)
+    }
+
+    fn run(&self, args: &[&str]) -> KubeResult<String> {
+        let key = args.join(" ");
+        self.record(format!("run {}", key));
+        Ok(self.outputs.get(&key).cloned().unwrap_or_default())
+    }
+
+    fn spawn(&self, args: &[&str]) -> KubeResult<Child> {
+        let key = args.join(" ");
+        self.record(format!("spawn {}", key));
+        let script = self
+            .scripts
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| "exec sleep 60".to_string());
+        Command::new("sh")
+            .args(["-c", &script])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())
+    }
+}
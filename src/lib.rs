@@ -0,0 +1,23 @@
+pub mod app;
+pub mod bulk;
+pub mod clipboard;
+pub mod debug;
+pub mod delete;
+pub mod describe;
+pub mod edit;
+pub mod events;
+pub mod filter;
+pub mod kube;
+pub mod logs;
+pub mod manifest;
+pub mod metrics;
+pub mod nodes;
+pub mod pods;
+pub mod portforward;
+pub mod process;
+pub mod resources;
+pub mod secrets;
+pub mod ui;
+pub mod watch;
+pub mod worker;
+pub mod workloads;
@@ -0,0 +1,277 @@
+use std::{collections::VecDeque, error::Error, sync::Arc};
+
+use crate::kube::KubeBackend;
+use crate::process::ChildStream;
+
+/// Upper bound on buffered log lines; older lines are dropped first.
+const MAX_LOG_LINES: usize = 10_000;
+
+pub struct LogView {
+    pub namespace: String,
+    pub pod: String,
+    pub container: Option<String>,
+
+    pub previous: bool,
+    pub timestamps: bool,
+    pub follow: bool,
+    pub finished: bool,
+
+    pub lines: VecDeque<String>,
+    pub scroll: usize,
+    pub page_height: usize,
+
+    kube: Arc<dyn KubeBackend>,
+    stream: Option<ChildStream<String>>,
+}
+
+impl LogView {
+    /// Starts streaming through `kube`, which is kept for restarts.
+    pub fn new(
+        kube: Arc<dyn KubeBackend>,
+        namespace: &str,
+        pod: &str,
+        container: Option<&str>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut view = LogView {
+            namespace: namespace.to_string(),
+            pod: pod.to_string(),
+            container: container.map(|c| c.to_string()),
+
+            previous: false,
+            timestamps: false,
+            follow: true,
+            finished: false,
+
+            lines: VecDeque::new(),
+            scroll: 0,
+            page_height: 0,
+
+            kube,
+            stream: None,
+        };
+        view.start()?;
+        Ok(view)
+    }
+
+    fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "logs".to_string(),
+            "-f".to_string(),
+            "-n".to_string(),
+            self.namespace.clone(),
+            self.pod.clone(),
+        ];
+        if let Some(container) = &self.container {
+            args.push("-c".to_string());
+            args.push(container.clone());
+        }
+        if self.previous {
+            args.push("--previous".to_string());
+        }
+        if self.timestamps {
+            args.push("--timestamps".to_string());
+        }
+        args
+    }
+
+    fn start(&mut self) -> Result<(), Box<dyn Error>> {
+        let args = self.args();
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+        let child = self.kube.spawn(&args)?;
+        self.stream = Some(ChildStream::new(child, |line| line, |line| line));
+        self.finished = false;
+        Ok(())
+    }
+
+    /// Restarts the stream with the current options, discarding the buffer.
+    pub fn restart(&mut self) -> Result<(), Box<dyn Error>> {
+        self.stream = None;
+        self.lines.clear();
+        self.scroll = 0;
+        self.follow = true;
+        self.start()
+    }
+
+    /// Moves any lines received since the last call into the buffer.
+    pub fn poll(&mut self) {
+        let Some(stream) = &mut self.stream else {
+            return;
+        };
+        let (lines, exited) = stream.poll();
+        if exited.is_some() {
+            self.finished = true;
+            self.stream = None;
+        }
+
+        for line in lines {
+            self.lines.push_back(line);
+            if self.lines.len() > MAX_LOG_LINES {
+                self.lines.pop_front();
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+        }
+
+        if self.follow {
+            self.scroll = self.max_scroll();
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height)
+    }
+
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.follow = false;
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll = (self.scroll + amount).min(self.max_scroll());
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.follow = false;
+        self.scroll = 0;
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.follow = true;
+        self.scroll = self.max_scroll();
+    }
+
+    pub fn toggle_follow(&mut self) {
+        if self.follow {
+            self.follow = false;
+        } else {
+            self.scroll_bottom();
+        }
+    }
+
+    pub fn toggle_previous(&mut self) -> Result<(), Box<dyn Error>> {
+        self.previous = !self.previous;
+        self.restart()
+    }
+
+    pub fn toggle_timestamps(&mut self) -> Result<(), Box<dyn Error>> {
+        self.timestamps = !self.timestamps;
+        self.restart()
+    }
+
+    pub fn visible_lines(&self) -> impl Iterator<Item = &String> {
+        self.lines.iter().skip(self.scroll).take(self.page_height)
+    }
+
+    pub fn title(&self) -> String {
+        let mut title = format!("Logs: {}", self.pod);
+        if let Some(container) = &self.container {
+            title.push_str(&format!(" [{}]", container));
+        }
+        title.push_str(if self.follow { " (following)" } else { " (paused)" });
+        if self.previous {
+            title.push_str(" (previous)");
+        }
+        if self.timestamps {
+            title.push_str(" (timestamps)");
+        }
+        if self.finished {
+            title.push_str(" (ended)");
+        }
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{self, Sender};
+
+    use super::*;
+    use crate::kube::fake::FakeKube;
+
+    /// A view fed from the returned sender instead of kubectl.
+    fn view(page_height: usize) -> (LogView, Sender<String>) {
+        let (tx, rx) = mpsc::channel();
+        let view = LogView {
+            namespace: "default".to_string(),
+            pod: "api-1".to_string(),
+            container: None,
+            previous: false,
+            timestamps: false,
+            follow: true,
+            finished: false,
+            lines: VecDeque::new(),
+            scroll: 0,
+            page_height,
+            kube: Arc::new(FakeKube::default()),
+            stream: Some(ChildStream::from_receiver(rx)),
+        };
+        (view, tx)
+    }
+
+    fn send(tx: &Sender<String>, range: std::ops::Range<usize>) {
+        for i in range {
+            tx.send(i.to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn buffer_drops_oldest_lines_past_the_limit() {
+        let (mut view, tx) = view(10);
+        send(&tx, 0..MAX_LOG_LINES + 5);
+        drop(tx);
+        view.poll();
+
+        assert_eq!(view.lines.len(), MAX_LOG_LINES);
+        assert_eq!(view.lines.front().map(|l| l.as_str()), Some("5"));
+        assert!(view.finished);
+        assert!(view.title().ends_with("(ended)"));
+    }
+
+    #[test]
+    fn following_sticks_to_the_bottom_until_scrolled_up() {
+        let (mut view, tx) = view(10);
+        send(&tx, 0..30);
+        view.poll();
+        assert_eq!(view.scroll, 20);
+        assert_eq!(view.visible_lines().next().map(|l| l.as_str()), Some("20"));
+
+        view.scroll_up(5);
+        assert!(!view.follow);
+        send(&tx, 30..40);
+        view.poll();
+        assert_eq!(view.scroll, 15);
+        view.scroll_down(100);
+        assert_eq!(view.scroll, 30);
+
+        view.scroll_top();
+        assert_eq!((view.scroll, view.follow), (0, false));
+        view.toggle_follow();
+        assert_eq!((view.scroll, view.follow), (30, true));
+        view.toggle_follow();
+        assert!(!view.follow);
+    }
+
+    #[test]
+    fn paused_view_keeps_its_place_while_old_lines_are_dropped() {
+        let (mut view, tx) = view(10);
+        send(&tx, 0..MAX_LOG_LINES);
+        view.poll();
+        view.scroll_up(100);
+        let first = view.visible_lines().next().cloned();
+
+        send(&tx, MAX_LOG_LINES..MAX_LOG_LINES + 50);
+        view.poll();
+        assert_eq!(view.visible_lines().next().cloned(), first);
+    }
+
+    #[test]
+    fn args_carry_the_container_and_options() {
+        let (mut view, _tx) = view(10);
+        view.container = Some("web".to_string());
+        view.previous = true;
+        view.timestamps = true;
+        assert_eq!(
+            view.args().join(" "),
+            "logs -f -n default api-1 -c web --previous --timestamps"
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use tui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+};
+
+/// A resource's YAML as returned by `kubectl get -o yaml`, with optional
+/// hiding of the noisy `metadata.managedFields` and `status` blocks.
+pub struct ManifestView {
+    pub kind: String,
+    pub name: String,
+    pub yaml: String,
+
+    /// Lines currently shown, each with its 1-based line number in `yaml`.
+    pub lines: Vec<(usize, String)>,
+    pub hide_noise: bool,
+
+    pub scroll: usize,
+    pub scroll_x: u16,
+    pub page_height: usize,
+
+    pub search: String,
+    pub searching: bool,
+    /// Indices into `lines` that contain `search`.
+    pub matches: Vec<usize>,
+    pub current_match: usize,
+}
+
+impl ManifestView {
+    pub fn new(kind: &str, name: &str, yaml: &str) -> Self {
+        let mut view = ManifestView {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            yaml: yaml.to_string(),
+            lines: Vec::new(),
+            hide_noise: true,
+            scroll: 0,
+            scroll_x: 0,
+            page_height: 0,
+            search: String::new(),
+            searching: false,
+            matches: Vec::new(),
+            current_match: 0,
+        };
+        view.refresh_lines();
+        view
+    }
+
+    fn refresh_lines(&mut self) {
+        let numbered = self
+            .yaml
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.to_string()));
+        self.lines = if self.hide_noise {
+            strip_noise(numbered)
+        } else {
+            numbered.collect()
+        };
+        self.scroll = self.scroll.min(self.max_scroll());
+        self.find_matches();
+    }
+
+    pub fn toggle_hide_noise(&mut self) {
+        self.hide_noise = !self.hide_noise;
+        self.refresh_lines();
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height.max(1))
+    }
+
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll = (self.scroll + amount).min(self.max_scroll());
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.scroll = self.max_scroll();
+    }
+
+    pub fn scroll_left(&mut self) {
+        self.scroll_x = self.scroll_x.saturating_sub(4);
+    }
+
+    pub fn scroll_right(&mut self) {
+        self.scroll_x = self.scroll_x.saturating_add(4);
+    }
+
+    pub fn start_search(&mut self) {
+        self.searching = true;
+        self.search.clear();
+        self.matches.clear();
+    }
+
+    /// Ends search input and jumps to the first match at or below the top
+    /// of the page.
+    pub fn finish_search(&mut self) {
+        self.searching = false;
+        self.find_matches();
+        self.current_match = self
+            .matches
+            .iter()
+            .position(|&m| m >= self.scroll)
+            .unwrap_or(0);
+        self.show_current_match();
+    }
+
+    pub fn cancel_search(&mut self) {
+        self.searching = false;
+        self.search.clear();
+        self.matches.clear();
+    }
+
+    fn find_matches(&mut self) {
+        let needle = self.search.to_lowercase();
+        self.matches = if needle.is_empty() {
+            Vec::new()
+        } else {
+            self.lines
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, line))| line.to_lowercase().contains(&needle))
+                .map(|(i, _)| i)
+                .collect()
+        };
+        self.current_match = self.current_match.min(self.matches.len().saturating_sub(1));
+    }
+
+    pub fn next_match(&mut self) {
+        if !self.matches.is_empty() {
+            self.current_match = (self.current_match + 1) % self.matches.len();
+            self.show_current_match();
+        }
+    }
+
+    pub fn previous_match(&mut self) {
+        if !self.matches.is_empty() {
+            let len = self.matches.len();
+            self.current_match = (self.current_match + len - 1) % len;
+            self.show_current_match();
+        }
+    }
+
+    fn show_current_match(&mut self) {
+        let Some(&line) = self.matches.get(self.current_match) else {
+            return;
+        };
+        let page = self.page_height.max(1);
+        if line < self.scroll || line >= self.scroll + page {
+            self.scroll = line.saturating_sub(page / 2).min(self.max_scroll());
+        }
+    }
+
+    /// The YAML to hand to an editor: without `managedFields` and `status`,
+    /// which `kubectl apply` would reject or ignore.
+    pub fn editable_yaml(&self) -> String {
+        let numbered = self.yaml.lines().enumerate().map(|(i, l)| (i, l.to_string()));
+        let mut yaml: String = strip_noise(numbered)
+            .into_iter()
+            .map(|(_, line)| line + "\n")
+            .collect();
+        if yaml.is_empty() {
+            yaml = self.yaml.clone();
+        }
+        yaml
+    }
+
+    pub fn title(&self) -> String {
+        let mut title = format!("YAML {}/{}", self.kind, self.name);
+        if self.searching {
+            title.push_str(&format!(" /{}", self.search));
+        } else if !self.search.is_empty() {
+            title.push_str(&format!(
+                " /{} [{}/{}]",
+                self.search,
+                if self.matches.is_empty() { 0 } else { self.current_match + 1 },
+                self.matches.len()
+            ));
+        }
+        title
+    }
+
+    /// The visible page, with line numbers, YAML colouring and search hits.
+    pub fn visible_spans(&self) -> Vec<Spans<'_>> {
+        let width = self.lines.last().map_or(1, |(n, _)| n.to_string().len());
+        self.lines
+            .iter()
+            .skip(self.scroll)
+            .take(self.page_height)
+            .map(|(number, line)| {
+                let mut spans = vec![Span::styled(
+                    format!("{:>width$} ", number, width = width),
+                    Style::default().fg(Color::DarkGray),
+                )];
+                spans.extend(highlight_search(highlight_yaml(line), &self.search));
+                Spans::from(spans)
+            })
+            .collect()
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Drops `managedFields` and the top-level `status` block. Block sequences
+/// in kubectl output sit at the same indent as their key, so `- ` lines at
+/// that indent belong to the block too.
+fn strip_noise(lines: impl Iterator<Item = (usize, String)>) -> Vec<(usize, String)> {
+    let mut kept = Vec::new();
+    let mut skipping: Option<usize> = None;
+
+    for (number, line) in lines {
+        let indent = indent_of(&line);
+        let trimmed = line.trim_start();
+
+        if let Some(block_indent) = skipping {
+            let inside = indent > block_indent
+                || (indent == block_indent && trimmed.starts_with("- "))
+                || trimmed.is_empty();
+            if inside {
+                continue;
+            }
+            skipping = None;
+        }
+
+        let is_noise = trimmed.starts_with("managedFields:")
+            || (indent == 0 && trimmed.starts_with("status:"));
+        if is_noise {
+            skipping = Some(indent);
+            continue;
+        }
+        kept.push((number, line));
+    }
+    kept
+}
+
+fn highlight_yaml(line: &str) -> Vec<Span<'_>> {
+    let indent = indent_of(line);
+    let (lead, rest) = line.split_at(indent);
+    let mut spans = vec![Span::raw(lead)];
+
+    if rest.starts_with('#') {
+        spans.push(Span::styled(rest, Style::default().fg(Color::DarkGray)));
+        return spans;
+    }
+    if rest == "---" {
+        spans.push(Span::styled(rest, Style::default().fg(Color::DarkGray)));
+        return spans;
+    }
+
+    let mut rest = rest;
+    while let Some(item) = rest.strip_prefix("- ") {
+        spans.push(Span::styled("- ", Style::default().fg(Color::Yellow)));
+        rest = item;
+    }
+
+    match split_key(rest) {
+        Some((key, value)) => {
+            spans.push(Span::styled(key, Style::default().fg(Color::Cyan)));
+            spans.push(Span::raw(":"));
+            if !value.is_empty() {
+                spans.push(Span::styled(value, value_style(value.trim())));
+            }
+        }
+        None => spans.push(Span::styled(rest, value_style(rest))),
+    }
+    spans
+}
+
+/// Splits `key: value` or `key:` at the first colon that ends the key.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with('"') || text.starts_with('\'') {
+        return None;
+    }
+    let colon = text
+        .match_indices(':')
+        .map(|(i, _)| i)
+        .find(|&i| text[i + 1..].is_empty() || text[i + 1..].starts_with(' '))?;
+    Some((&text[..colon], &text[colon + 1..]))
+}
+
+fn value_style(value: &str) -> Style {
+    match value {
+        "true" | "false" | "null" | "~" => Style::default().fg(Color::Magenta),
+        v if v.parse::<f64>().is_ok() => Style::default().fg(Color::Magenta),
+        v if v.starts_with('"') || v.starts_with('\'') => Style::default().fg(Color::Green),
+        "|" | ">" | "|-" | ">-" => Style::default().fg(Color::DarkGray),
+        _ => Style::default().fg(Color::Green),
+    }
+}
+
+/// Re-splits `spans` so that case-insensitive occurrences of `needle` stand out.
+fn highlight_search<'a>(spans: Vec<Span<'a>>, needle: &str) -> Vec<Span<'a>> {
+    if needle.is_empty() {
+        return spans;
+    }
+    let needle = needle.to_lowercase();
+    let hit = Style::default()
+        .bg(Color::Yellow)
+        .fg(Color::Black)
+        .add_modifier(Modifier::BOLD);
+
+    let mut result = Vec::new();
+    for span in spans {
+        let text = span.content.to_string();
+        let (lower, origins) = lowercase_with_origins(&text);
+
+        let mut start = 0;
+        let mut searched = 0;
+        while let Some(offset) = lower[searched..].find(&needle) {
+            let found = searched + offset;
+            // Matches are widened to whole characters of the original text.
+            let begin = origins[found].0.max(start);
+            let end = origins[found + needle.len() - 1].1;
+            if begin > start {
+                result.push(Span::styled(text[start..begin].to_string(), span.style));
+            }
+            if end > begin {
+                result.push(Span::styled(text[begin..end].to_string(), hit));
+            }
+            start = start.max(end);
+            searched = found + needle.len();
+        }
+        if start < text.len() {
+            result.push(Span::styled(text[start..].to_string(), span.style));
+        }
+    }
+    result
+}
+
+/// Lowercases `text`, returning for every byte of the result the byte range of
+/// the character in `text` it came from. Lowercasing can change how many bytes
+/// a character takes, so offsets into the result cannot be used on `text`.
+fn lowercase_with_origins(text: &str) -> (String, Vec<(usize, usize)>) {
+    let mut lower = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len());
+    for (begin, c) in text.char_indices() {
+        let end = begin + c.len_utf8();
+        for l in c.to_lowercase() {
+            lower.push(l);
+            origins.extend(std::iter::repeat_n((begin, end), l.len_utf8()));
+        }
+    }
+    (lower, origins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = "\
+apiVersion: v1
+kind: Pod
+metadata:
+  name: api-1
+  managedFields:
+  - manager: kubectl
+spec:
+  containers:
+  - name: api
+status:
+  phase: Running
+";
+
+    fn highlighted(spans: &[Span]) -> Vec<String> {
+        spans
+            .iter()
+            .filter(|s| s.style.bg == Some(Color::Yellow))
+            .map(|s| s.content.to_string())
+            .collect()
+    }
+
+    fn text(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn hides_managed_fields_and_status_until_toggled() {
+        let mut view = ManifestView::new("pod", "api-1", YAML);
+        let shown: Vec<usize> = view.lines.iter().map(|(n, _)| *n).collect();
+        assert_eq!(shown, vec![1, 2, 3, 4, 7, 8, 9]);
+
+        view.toggle_hide_noise();
+        assert_eq!(view.lines.len(), 11);
+    }
+
+    #[test]
+    fn search_is_case_insensitive_and_wraps() {
+        let mut view = ManifestView::new("pod", "api-1", YAML);
+        view.toggle_hide_noise();
+        view.start_search();
+        view.search = "API".to_string();
+        view.finish_search();
+
+        assert_eq!(view.matches, vec![0, 3, 8]);
+        view.next_match();
+        view.next_match();
+        view.next_match();
+        assert_eq!(view.current_match, 0);
+        view.previous_match();
+        assert_eq!(view.current_match, 2);
+    }
+
+    #[test]
+    fn highlight_search_splits_spans_around_matches() {
+        let spans = highlight_search(vec![Span::raw("api and API")], "api");
+        assert_eq!(highlighted(&spans), vec!["api", "API"]);
+        assert_eq!(text(&spans), "api and API");
+    }
+
+    #[test]
+    fn highlight_search_maps_matches_back_through_wider_characters() {
+        // 'ẞ' and 'Ⱥ' shrink and grow by a byte when lowercased, so the total
+        // length stays the same while every later offset shifts.
+        let spans = highlight_search(vec![Span::raw("ẞȺ name")], "name");
+        assert_eq!(highlighted(&spans), vec!["name"]);
+        assert_eq!(text(&spans), "ẞȺ name");
+
+        let spans = highlight_search(vec![Span::raw("xẞȺy")], "ßⱥ");
+        assert_eq!(highlighted(&spans), vec!["ẞȺ"]);
+        assert_eq!(text(&spans), "xẞȺy");
+    }
+
+    #[test]
+    fn highlight_search_widens_partial_characters() {
+        // 'İ' lowercases to "i̇", so a search for "i" matches half of it.
+        let spans = highlight_search(vec![Span::raw("İstanbul")], "i");
+        assert_eq!(highlighted(&spans), vec!["İ"]);
+        assert_eq!(text(&spans), "İstanbul");
+    }
+}
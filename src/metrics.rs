@@ -0,0 +1,280 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Samples kept per pod or node, oldest first.
+const HISTORY_LEN: usize = 30;
+/// Samples drawn in a sparkline.
+pub const SPARKLINE_WIDTH: usize = 8;
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Wait before retrying `kubectl top` after the first failure; it doubles
+/// with each further failure up to `MAX_RETRY_DELAY`.
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(600);
+
+/// CPU and memory use as reported by `kubectl top`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Usage {
+    pub cpu_millis: u64,
+    pub memory_bytes: u64,
+}
+
+impl Usage {
+    pub fn cpu_display(&self) -> String {
+        format!("{}m", self.cpu_millis)
+    }
+
+    pub fn memory_display(&self) -> String {
+        format!("{}Mi", self.memory_bytes >> 20)
+    }
+}
+
+/// Parses a CPU quantity such as `250m` or `2` into millicores.
+pub fn parse_cpu(quantity: &str) -> Option<u64> {
+    match quantity.strip_suffix('m') {
+        Some(millis) => millis.parse().ok(),
+        None => quantity.parse::<f64>().ok().map(|cores| (cores * 1000.0).round() as u64),
+    }
+}
+
+/// Parses a binary memory quantity such as `128Mi` into bytes.
+pub fn parse_memory(quantity: &str) -> Option<u64> {
+    let units = [("Ki", 1u64 << 10), ("Mi", 1 << 20), ("Gi", 1 << 30), ("Ti", 1 << 40)];
+    units
+        .iter()
+        .find_map(|(suffix, factor)| {
+            let value: u64 = quantity.strip_suffix(suffix)?.parse().ok()?;
+            Some(value * factor)
+        })
+        .or_else(|| quantity.parse().ok())
+}
+
+fn usage(cpu: &str, memory: &str) -> Option<Usage> {
+    Some(Usage {
+        cpu_millis: parse_cpu(cpu)?,
+        memory_bytes: parse_memory(memory)?,
+    })
+}
+
+/// Parses `kubectl top pods --no-headers` into usage by `namespace/name`.
+/// Without a namespace the output is expected from `--all-namespaces`.
+pub fn parse_top_pods(output: &str, namespace: Option<&str>) -> HashMap<String, Usage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (namespace, rest) = match namespace {
+                Some(namespace) => (namespace, &fields[..]),
+                None => (*fields.first()?, fields.get(1..)?),
+            };
+            match rest {
+                [name, cpu, memory, ..] => {
+                    Some((format!("{}/{}", namespace, name), usage(cpu, memory)?))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parses `kubectl top nodes --no-headers` into usage by node name. Nodes
+/// without metrics yet are left out.
+pub fn parse_top_nodes(output: &str) -> HashMap<String, Usage> {
+    output
+        .lines()
+        .filter_map(|line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [name, cpu, _, memory, ..] => Some((name.to_string(), usage(cpu, memory)?)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Recent usage samples by pod or node, kept while kubetui runs.
+#[derive(Default)]
+pub struct UsageHistory {
+    samples: HashMap<String, VecDeque<Usage>>,
+}
+
+impl UsageHistory {
+    /// Adds the latest samples and forgets keys starting with `scope` that
+    /// they leave out, such as deleted pods. An empty scope covers every key.
+    pub fn record(&mut self, usage: HashMap<String, Usage>, scope: &str) {
+        self.samples
+            .retain(|key, _| !key.starts_with(scope) || usage.contains_key(key));
+        for (key, sample) in usage {
+            let samples = self.samples.entry(key).or_default();
+            if samples.len() == HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+        }
+    }
+
+    pub fn latest(&self, key: &str) -> Option<Usage> {
+        self.samples.get(key)?.back().copied()
+    }
+
+    /// The most recent CPU samples of `key` as a sparkline.
+    pub fn cpu_sparkline(&self, key: &str) -> String {
+        let Some(samples) = self.samples.get(key) else {
+            return String::new();
+        };
+        let values: Vec<u64> = samples
+            .iter()
+            .skip(samples.len().saturating_sub(SPARKLINE_WIDTH))
+            .map(|s| s.cpu_millis)
+            .collect();
+        sparkline(&values)
+    }
+}
+
+/// Draws `values` as bars scaled to the largest of them.
+pub fn sparkline(values: &[u64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    values
+        .iter()
+        .map(|v| BARS[(v * (BARS.len() as u64 - 1) / max) as usize])
+        .collect()
+}
+
+/// Usage of the pods and nodes on screen. While `kubectl top` fails, usually
+/// because metrics-server is missing, it is retried less and less often.
+#[derive(Default)]
+pub struct Metrics {
+    pub pods: UsageHistory,
+    pub nodes: UsageHistory,
+    /// Delay after the latest failure, zero while `kubectl top` works.
+    backoff: Duration,
+    retry_at: Option<Instant>,
+}
+
+impl Metrics {
+    /// Whether `kubectl top` is worth running at `now`.
+    pub fn due(&self, now: Instant) -> bool {
+        self.retry_at.is_none_or(|at| now >= at)
+    }
+
+    /// Backs off after a failure at `now`. Returns whether it is the first
+    /// failure since metrics last worked.
+    pub fn failed(&mut self, now: Instant) -> bool {
+        let first = self.backoff.is_zero();
+        self.backoff = if first {
+            RETRY_DELAY
+        } else {
+            (self.backoff * 2).min(MAX_RETRY_DELAY)
+        };
+        self.retry_at = Some(now + self.backoff);
+        first
+    }
+
+    /// Forgets earlier failures, after a success or on a new cluster.
+    pub fn reset(&mut self) {
+        self.backoff = Duration::ZERO;
+        self.retry_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(cpu_millis: u64) -> Usage {
+        Usage {
+            cpu_millis,
+            memory_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn parses_cpu_and_memory_quantities() {
+        assert_eq!(parse_cpu("250m"), Some(250));
+        assert_eq!(parse_cpu("2"), Some(2000));
+        assert_eq!(parse_cpu("0.5"), Some(500));
+        assert_eq!(parse_cpu("lots"), None);
+
+        assert_eq!(parse_memory("128Mi"), Some(128 << 20));
+        assert_eq!(parse_memory("16318440Ki"), Some(16_318_440 << 10));
+        assert_eq!(parse_memory("1Gi"), Some(1 << 30));
+        assert_eq!(parse_memory("4096"), Some(4096));
+        assert_eq!(parse_memory("1.5Gi"), None);
+    }
+
+    #[test]
+    fn parses_top_pods_in_one_or_all_namespaces() {
+        let output = "api-1         3m           21Mi
+                      web-7d4-a     250m         128Mi
+                      starting
+";
+        let usage = parse_top_pods(output, Some("default"));
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage["default/api-1"].cpu_display(), "3m");
+        assert_eq!(usage["default/web-7d4-a"].memory_display(), "128Mi");
+
+        let output = "kube-system   coredns-76f75df574-9xk2p   2m     14Mi
+                      payments      billing-0                  1      1Gi
+";
+        let usage = parse_top_pods(output, None);
+        assert_eq!(usage["kube-system/coredns-76f75df574-9xk2p"].cpu_millis, 2);
+        assert_eq!(usage["payments/billing-0"].cpu_millis, 1000);
+        assert_eq!(usage["payments/billing-0"].memory_display(), "1024Mi");
+    }
+
+    #[test]
+    fn parses_top_nodes_and_skips_those_without_metrics() {
+        let output = "control-plane   183m         4%     1721Mi          22%
+                      worker-1        950m         23%    4096Mi          51%
+                      worker-2        <unknown>    <unknown>   <unknown>   <unknown>
+";
+        let usage = parse_top_nodes(output);
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage["control-plane"].cpu_millis, 183);
+        assert_eq!(usage["worker-1"].memory_bytes, 4096 << 20);
+        assert!(!usage.contains_key("worker-2"));
+    }
+
+    #[test]
+    fn history_forgets_keys_missing_from_the_latest_sample_in_scope() {
+        let mut history = UsageHistory::default();
+        history.record(
+            HashMap::from([
+                ("default/web".to_string(), usage(100)),
+                ("default/api".to_string(), usage(50)),
+                ("payments/billing".to_string(), usage(10)),
+            ]),
+            "",
+        );
+        history.record(HashMap::from([("default/web".to_string(), usage(200))]), "default/");
+
+        assert_eq!(history.cpu_sparkline("default/web"), "▄█");
+        assert_eq!(history.latest("default/api"), None);
+        assert_eq!(history.latest("payments/billing"), Some(usage(10)));
+
+        history.record(HashMap::new(), "");
+        assert_eq!(history.latest("payments/billing"), None);
+    }
+
+    #[test]
+    fn failures_back_off_until_metrics_work_again() {
+        let mut metrics = Metrics::default();
+        let now = Instant::now();
+        assert!(metrics.due(now));
+
+        assert!(metrics.failed(now));
+        assert!(!metrics.due(now + RETRY_DELAY / 2));
+        assert!(metrics.due(now + RETRY_DELAY));
+
+        let later = now + RETRY_DELAY;
+        assert!(!metrics.failed(later));
+        assert!(!metrics.due(later + RETRY_DELAY));
+        assert!(metrics.due(later + RETRY_DELAY * 2));
+        for _ in 0..10 {
+            metrics.failed(later);
+        }
+        assert!(metrics.due(later + MAX_RETRY_DELAY));
+
+        metrics.reset();
+        assert!(metrics.due(now));
+        assert!(metrics.failed(now));
+    }
+}
@@ -0,0 +1,457 @@
+use std::{collections::HashMap, error::Error, process::Child};
+
+use serde_json::Value;
+
+use crate::metrics::{self, Usage};
+use crate::process::ChildStream;
+use crate::workloads::parse_items;
+
+const ROLE_LABEL: &str = "node-role.kubernetes.io/";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub name: String,
+    pub ready: bool,
+    /// Cordoned nodes accept no new pods.
+    pub unschedulable: bool,
+    pub roles: Vec<String>,
+    pub version: String,
+    /// Allocatable CPU and memory as Kubernetes quantities.
+    pub cpu: String,
+    pub memory: String,
+    /// Taints as `key=value:Effect`.
+    pub taints: Vec<String>,
+    pub pods: usize,
+}
+
+impl Node {
+    fn from_json(item: &Value) -> Option<Node> {
+        let metadata = &item["metadata"];
+        let status = &item["status"];
+        let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+
+        let ready = status["conditions"].as_array().is_some_and(|conditions| {
+            conditions
+                .iter()
+                .any(|c| c["type"] == "Ready" && c["status"] == "True")
+        });
+        let roles = metadata["labels"]
+            .as_object()
+            .map(|labels| {
+                labels
+                    .keys()
+                    .filter_map(|key| key.strip_prefix(ROLE_LABEL))
+                    .filter(|role| !role.is_empty())
+                    .map(|role| role.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let taints = item["spec"]["taints"]
+            .as_array()
+            .map(|taints| taints.iter().filter_map(taint_display).collect())
+            .unwrap_or_default();
+
+        Some(Node {
+            name: metadata["name"].as_str()?.to_string(),
+            ready,
+            unschedulable: item["spec"]["unschedulable"].as_bool().unwrap_or(false),
+            roles,
+            version: text(&status["nodeInfo"]["kubeletVersion"]),
+            cpu: text(&status["allocatable"]["cpu"]),
+            memory: text(&status["allocatable"]["memory"]),
+            taints,
+            pods: 0,
+        })
+    }
+
+    /// Mirrors the STATUS column of `kubectl get nodes`.
+    pub fn status_display(&self) -> String {
+        let status = if self.ready { "Ready" } else { "NotReady" };
+        if self.unschedulable {
+            format!("{},SchedulingDisabled", status)
+        } else {
+            status.to_string()
+        }
+    }
+
+    pub fn roles_display(&self) -> String {
+        if self.roles.is_empty() {
+            "<none>".to_string()
+        } else {
+            self.roles.join(",")
+        }
+    }
+
+    pub fn memory_display(&self) -> String {
+        format_memory(&self.memory)
+    }
+
+    /// CPU use with its share of allocatable, like `kubectl top nodes`.
+    pub fn cpu_usage_display(&self, usage: &Usage) -> String {
+        let allocatable = metrics::parse_cpu(&self.cpu);
+        with_share(usage.cpu_display(), usage.cpu_millis, allocatable)
+    }
+
+    pub fn memory_usage_display(&self, usage: &Usage) -> String {
+        let allocatable = metrics::parse_memory(&self.memory);
+        with_share(usage.memory_display(), usage.memory_bytes, allocatable)
+    }
+
+    pub fn taints_display(&self) -> String {
+        if self.taints.is_empty() {
+            "<none>".to_string()
+        } else {
+            self.taints.join(", ")
+        }
+    }
+}
+
+fn with_share(value: String, used: u64, total: Option<u64>) -> String {
+    match total {
+        Some(total) if total > 0 => format!("{} ({}%)", value, used * 100 / total),
+        _ => value,
+    }
+}
+
+fn taint_display(taint: &Value) -> Option<String> {
+    let key = taint["key"].as_str()?;
+    let effect = taint["effect"].as_str().unwrap_or_default();
+    Some(match taint["value"].as_str() {
+        Some(value) if !value.is_empty() => format!("{}={}:{}", key, value, effect),
+        _ => format!("{}:{}", key, effect),
+    })
+}
+
+/// Shows a binary memory quantity such as `16318440Ki` in GiB.
+fn format_memory(quantity: &str) -> String {
+    match metrics::parse_memory(quantity) {
+        Some(bytes) => format!("{:.1}Gi", bytes as f64 / (1u64 << 30) as f64),
+        None => quantity.to_string(),
+    }
+}
+
+/// Parses the output of `kubectl get nodes -o json`.
+pub fn parse_nodes(json: &str) -> Result<Vec<Node>, Box<dyn Error>> {
+    parse_items(json, Node::from_json)
+}
+
+/// Fills in pod counts from the space-separated node names of every pod.
+pub fn count_pods(nodes: &mut [Node], pod_nodes: &str) {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for node in pod_nodes.trim_matches('\'').split_whitespace() {
+        *counts.entry(node).or_default() += 1;
+    }
+    for node in nodes {
+        node.pods = counts.get(node.name.as_str()).copied().unwrap_or(0);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrainField {
+    IgnoreDaemonSets,
+    DeleteEmptyDirData,
+}
+
+impl DrainField {
+    pub const ALL: [DrainField; 2] =
+        [DrainField::IgnoreDaemonSets, DrainField::DeleteEmptyDirData];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DrainField::IgnoreDaemonSets => "Ignore DaemonSet pods",
+            DrainField::DeleteEmptyDirData => "Delete emptyDir data",
+        }
+    }
+}
+
+/// A drain waiting for its options to be confirmed.
+pub struct PendingDrain {
+    pub node: String,
+    pub focus: usize,
+    pub ignore_daemonsets: bool,
+    pub delete_emptydir_data: bool,
+}
+
+impl PendingDrain {
+    pub fn new(node: &str) -> Self {
+        PendingDrain {
+            node: node.to_string(),
+            focus: 0,
+            // Almost every cluster runs DaemonSets, which block a plain drain.
+            ignore_daemonsets: true,
+            delete_emptydir_data: false,
+        }
+    }
+
+    pub fn focused(&self) -> DrainField {
+        DrainField::ALL[self.focus]
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % DrainField::ALL.len();
+    }
+
+    pub fn focus_previous(&mut self) {
+        self.focus = (self.focus + DrainField::ALL.len() - 1) % DrainField::ALL.len();
+    }
+
+    pub fn is_set(&self, field: DrainField) -> bool {
+        match field {
+            DrainField::IgnoreDaemonSets => self.ignore_daemonsets,
+            DrainField::DeleteEmptyDirData => self.delete_emptydir_data,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        match self.focused() {
+            DrainField::IgnoreDaemonSets => self.ignore_daemonsets = !self.ignore_daemonsets,
+            DrainField::DeleteEmptyDirData => {
+                self.delete_emptydir_data = !self.delete_emptydir_data
+            }
+        }
+    }
+
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec!["drain".to_string(), self.node.clone()];
+        if self.ignore_daemonsets {
+            args.push("--ignore-daemonsets".to_string());
+        }
+        if self.delete_emptydir_data {
+            args.push("--delete-emptydir-data".to_string());
+        }
+        args
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrainStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Stopped,
+}
+
+impl DrainStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            DrainStatus::Running => "Draining",
+            DrainStatus::Succeeded => "Drained",
+            DrainStatus::Failed => "Failed",
+            DrainStatus::Stopped => "Stopped",
+        }
+    }
+}
+
+/// A running `kubectl drain`, tracking which pods it evicted. The process is
+/// killed when the drain is dropped.
+pub struct Drain {
+    pub node: String,
+    pub status: DrainStatus,
+    /// Everything kubectl printed, in order.
+    pub lines: Vec<String>,
+    /// Pods as `namespace/name` that kubectl started evicting.
+    pub evicting: Vec<String>,
+    /// Names of the pods that are gone.
+    pub evicted: Vec<String>,
+
+    stream: ChildStream<String>,
+}
+
+impl Drain {
+    /// Takes over a child started with `PendingDrain::args`.
+    pub fn new(node: &str, child: Child) -> Self {
+        Drain {
+            node: node.to_string(),
+            status: DrainStatus::Running,
+            lines: Vec::new(),
+            evicting: Vec::new(),
+            evicted: Vec::new(),
+            stream: ChildStream::new(child, |line| line, |line| line),
+        }
+    }
+
+    /// Picks up output since the last call and the exit code once kubectl
+    /// is done.
+    pub fn poll(&mut self) {
+        let (lines, exited) = self.stream.poll();
+        for line in lines {
+            self.record(line);
+        }
+        if let Some(success) = exited {
+            self.status = if success {
+                DrainStatus::Succeeded
+            } else {
+                DrainStatus::Failed
+            };
+        }
+    }
+
+    fn record(&mut self, line: String) {
+        if line.trim().is_empty() {
+            return;
+        }
+        if let Some(pod) = line.strip_prefix("evicting pod ") {
+            self.evicting.push(pod.trim().to_string());
+        } else if let Some(pod) = line.strip_prefix("pod/") {
+            if let Some(pod) = pod.strip_suffix(" evicted") {
+                self.evicted.push(pod.to_string());
+            }
+        }
+        self.lines.push(line);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.status == DrainStatus::Running
+    }
+
+    pub fn stop(&mut self) {
+        if self.stream.stop() {
+            self.status = DrainStatus::Stopped;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    const NODES: &str = r#"{"apiVersion": "v1", "kind": "List", "items": [
+        {
+            "metadata": {
+                "name": "control-plane",
+                "labels": {
+                    "kubernetes.io/hostname": "control-plane",
+                    "node-role.kubernetes.io/control-plane": "",
+                    "node-role.kubernetes.io/": ""
+                }
+            },
+            "spec": {
+                "taints": [
+                    {"key": "node-role.kubernetes.io/control-plane", "effect": "NoSchedule"},
+                    {"key": "dedicated", "value": "infra", "effect": "NoExecute"}
+                ]
+            },
+            "status": {
+                "allocatable": {"cpu": "3800m", "memory": "16318440Ki", "pods": "110"},
+                "conditions": [
+                    {"type": "MemoryPressure", "status": "False"},
+                    {"type": "Ready", "status": "True"}
+                ],
+                "nodeInfo": {"kubeletVersion": "v1.30.2"}
+            }
+        },
+        {
+            "metadata": {"name": "worker-1", "labels": {"kubernetes.io/os": "linux"}},
+            "spec": {"unschedulable": true},
+            "status": {
+                "allocatable": {"cpu": "4", "memory": "8Gi"},
+                "conditions": [{"type": "Ready", "status": "Unknown"}],
+                "nodeInfo": {"kubeletVersion": "v1.29.6"}
+            }
+        },
+        {"metadata": {}}
+    ]}"#;
+
+    #[test]
+    fn parses_nodes_like_kubectl_shows_them() {
+        let nodes = parse_nodes(NODES).unwrap();
+        assert_eq!(nodes.len(), 2);
+
+        let control = &nodes[0];
+        assert_eq!(control.name, "control-plane");
+        assert_eq!(control.status_display(), "Ready");
+        assert_eq!(control.roles_display(), "control-plane");
+        assert_eq!(control.version, "v1.30.2");
+        assert_eq!(control.memory_display(), "15.6Gi");
+        assert_eq!(
+            control.taints_display(),
+            "node-role.kubernetes.io/control-plane:NoSchedule, dedicated=infra:NoExecute"
+        );
+
+        let worker = &nodes[1];
+        assert_eq!(worker.status_display(), "NotReady,SchedulingDisabled");
+        assert_eq!(worker.roles_display(), "<none>");
+        assert_eq!(worker.taints_display(), "<none>");
+        assert_eq!(worker.memory_display(), "8.0Gi");
+
+        assert!(parse_nodes("not json").is_err());
+    }
+
+    #[test]
+    fn usage_is_shown_with_its_share_of_allocatable() {
+        let nodes = parse_nodes(NODES).unwrap();
+        let usage = Usage {
+            cpu_millis: 950,
+            memory_bytes: 4 << 30,
+        };
+        assert_eq!(nodes[0].cpu_usage_display(&usage), "950m (25%)");
+        assert_eq!(nodes[1].memory_usage_display(&usage), "4096Mi (50%)");
+
+        let unknown = Node {
+            cpu: String::new(),
+            ..nodes[1].clone()
+        };
+        assert_eq!(unknown.cpu_usage_display(&usage), "950m");
+    }
+
+    #[test]
+    fn counts_pods_from_quoted_jsonpath_output() {
+        let mut nodes = parse_nodes(NODES).unwrap();
+        count_pods(&mut nodes, "'control-plane worker-1 control-plane gone'");
+        assert_eq!(nodes[0].pods, 2);
+        assert_eq!(nodes[1].pods, 1);
+
+        // Pending pods have no node, which leaves an empty list.
+        count_pods(&mut nodes, "''");
+        assert_eq!(nodes[0].pods, 0);
+    }
+
+    #[test]
+    fn drain_args_follow_the_chosen_options() {
+        let mut pending = PendingDrain::new("worker-1");
+        assert_eq!(pending.args(), ["drain", "worker-1", "--ignore-daemonsets"]);
+
+        pending.toggle();
+        pending.focus_previous();
+        assert_eq!(pending.focused(), DrainField::DeleteEmptyDirData);
+        pending.toggle();
+        assert_eq!(pending.args(), ["drain", "worker-1", "--delete-emptydir-data"]);
+        assert!(!pending.is_set(DrainField::IgnoreDaemonSets));
+    }
+
+    #[test]
+    fn drain_output_tracks_evictions() {
+        let (tx, rx) = mpsc::channel();
+        let output = "node/worker-1 cordoned\n\
+            Warning: ignoring DaemonSet-managed Pods: kube-system/kube-proxy-x7k2p\n\
+            evicting pod default/web-2\n\
+            evicting pod payments/billing-0\n\
+            \n\
+            pod/billing-0 evicted\n\
+            pod/web-2 evicted\n\
+            node/worker-1 drained";
+        for line in output.lines() {
+            tx.send(line.to_string()).unwrap();
+        }
+        let mut drain = Drain {
+            node: "worker-1".to_string(),
+            status: DrainStatus::Running,
+            lines: Vec::new(),
+            evicting: Vec::new(),
+            evicted: Vec::new(),
+            stream: ChildStream::from_receiver(rx),
+        };
+
+        drain.poll();
+        assert!(drain.is_running());
+        assert_eq!(drain.evicting, ["default/web-2", "payments/billing-0"]);
+        assert_eq!(drain.evicted, ["billing-0", "web-2"]);
+        assert_eq!(drain.lines.len(), 7);
+
+        drop(tx);
+        drain.poll();
+        assert_eq!(drain.status, DrainStatus::Failed);
+    }
+}
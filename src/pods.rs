@@ -0,0 +1,367 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pod {
+    pub name: String,
+    pub namespace: String,
+    pub status: String,
+    pub ready: usize,
+    pub total: usize,
+    pub restarts: u64,
+    /// Creation time as seconds since the Unix epoch.
+    pub created: Option<i64>,
+    pub node: Option<String>,
+    pub ip: Option<String>,
+    /// Kind and name of the controller that owns the pod, e.g. a ReplicaSet.
+    pub owner: Option<(String, String)>,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl Pod {
+    fn from_json(item: &Value) -> Option<Pod> {
+        let metadata = &item["metadata"];
+        let spec = &item["spec"];
+        let status = &item["status"];
+
+        let name = metadata["name"].as_str()?.to_string();
+        let statuses = status["containerStatuses"]
+            .as_array()
+            .map(|s| s.as_slice())
+            .unwrap_or_default();
+
+        let total = spec["containers"].as_array().map_or(statuses.len(), |c| c.len());
+        let ready = statuses
+            .iter()
+            .filter(|s| s["ready"].as_bool().unwrap_or(false))
+            .count();
+        let restarts = statuses
+            .iter()
+            .filter_map(|s| s["restartCount"].as_u64())
+            .sum();
+
+        Some(Pod {
+            name,
+            namespace: metadata["namespace"].as_str().unwrap_or_default().to_string(),
+            status: pod_status(metadata, status, statuses),
+            ready,
+            total,
+            restarts,
+            created: metadata["creationTimestamp"].as_str().and_then(parse_timestamp),
+            node: spec["nodeName"].as_str().map(|s| s.to_string()),
+            ip: status["podIP"].as_str().map(|s| s.to_string()),
+            owner: controller(metadata),
+            labels: labels(metadata),
+        })
+    }
+
+    pub fn ready_display(&self) -> String {
+        format!("{}/{}", self.ready, self.total)
+    }
+
+    pub fn age_display(&self) -> String {
+        self.created.map_or_else(|| "<unknown>".to_string(), format_age)
+    }
+
+    /// The pod as `namespace/name`, which is how its metrics are keyed.
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+}
+
+fn controller(metadata: &Value) -> Option<(String, String)> {
+    let owners = metadata["ownerReferences"].as_array()?;
+    let owner = owners
+        .iter()
+        .find(|o| o["controller"].as_bool() == Some(true))
+        .or_else(|| owners.first())?;
+    Some((owner["kind"].as_str()?.to_string(), owner["name"].as_str()?.to_string()))
+}
+
+fn labels(metadata: &Value) -> BTreeMap<String, String> {
+    metadata["labels"]
+        .as_object()
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Mirrors the STATUS column of `kubectl get pods`: a terminating pod or a
+/// waiting/terminated container reason takes precedence over the pod phase.
+fn pod_status(metadata: &Value, status: &Value, statuses: &[Value]) -> String {
+    if !metadata["deletionTimestamp"].is_null() {
+        return "Terminating".to_string();
+    }
+
+    for container in statuses {
+        let state = &container["state"];
+        if let Some(reason) = state["waiting"]["reason"].as_str() {
+            return reason.to_string();
+        }
+        if let Some(reason) = state["terminated"]["reason"].as_str() {
+            if reason != "Completed" {
+                return reason.to_string();
+            }
+        }
+    }
+
+    status["reason"]
+        .as_str()
+        .or_else(|| status["phase"].as_str())
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+/// Parses the output of `kubectl get pods -o json`.
+pub fn parse_pods(json: &str) -> Result<Vec<Pod>, Box<dyn Error>> {
+    let value: Value = serde_json::from_str(json)?;
+    let items = value["items"]
+        .as_array()
+        .ok_or("Unexpected pod list format")?;
+    Ok(items.iter().filter_map(Pod::from_json).collect())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Container {
+    pub name: String,
+    /// Ephemeral debug containers added with `kubectl debug`.
+    pub ephemeral: bool,
+}
+
+impl Container {
+    pub fn display_name(&self) -> String {
+        if self.ephemeral {
+            format!("{} (ephemeral)", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// Parses the containers, including ephemeral ones, out of `kubectl get pod -o json`.
+pub fn parse_containers(json: &str) -> Result<Vec<Container>, Box<dyn Error>> {
+    let value: Value = serde_json::from_str(json)?;
+    let spec = &value["spec"];
+
+    let names = |key: &str| -> Vec<String> {
+        spec[key]
+            .as_array()
+            .map(|list| {
+                list.iter()
+                    .filter_map(|c| c["name"].as_str().map(|n| n.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let regular = names("containers").into_iter().map(|name| Container {
+        name,
+        ephemeral: false,
+    });
+    let ephemeral = names("ephemeralContainers").into_iter().map(|name| Container {
+        name,
+        ephemeral: true,
+    });
+    Ok(regular.chain(ephemeral).collect())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PodSortColumn {
+    Name,
+    Status,
+    Restarts,
+    Age,
+    Node,
+}
+
+impl PodSortColumn {
+    pub fn next(self) -> Self {
+        match self {
+            PodSortColumn::Name => PodSortColumn::Status,
+            PodSortColumn::Status => PodSortColumn::Restarts,
+            PodSortColumn::Restarts => PodSortColumn::Age,
+            PodSortColumn::Age => PodSortColumn::Node,
+            PodSortColumn::Node => PodSortColumn::Name,
+        }
+    }
+}
+
+pub fn sort_pods(pods: &mut [Pod], column: PodSortColumn, descending: bool) {
+    pods.sort_by(|a, b| {
+        let ordering = match column {
+            PodSortColumn::Name => a.name.cmp(&b.name),
+            PodSortColumn::Status => a.status.cmp(&b.status),
+            PodSortColumn::Restarts => a.restarts.cmp(&b.restarts),
+            // Ascending AGE means youngest first, so compare creation times in reverse.
+            PodSortColumn::Age => b.created.cmp(&a.created),
+            PodSortColumn::Node => a.node.cmp(&b.node),
+        }
+        .then_with(|| a.name.cmp(&b.name));
+
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+/// Parses an RFC 3339 UTC timestamp such as `2024-01-31T12:00:00Z`.
+pub fn parse_timestamp(ts: &str) -> Option<i64> {
+    let ts = ts.strip_suffix('Z')?;
+    let (date, time) = ts.split_once('T')?;
+
+    let mut date_parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let year = date_parts.next()??;
+    let month = date_parts.next()??;
+    let day = date_parts.next()??;
+
+    let time = time.split('.').next()?;
+    let mut time_parts = time.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+    let hour = time_parts.next()??;
+    let minute = time_parts.next()??;
+    let second = time_parts.next()??;
+
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+// Howard Hinnant's days_from_civil algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+pub fn now_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Formats the time since `created` the way kubectl's AGE column does.
+pub fn format_age(created: i64) -> String {
+    let secs = (now_timestamp() - created).max(0);
+    match secs {
+        s if s < 120 => format!("{}s", s),
+        s if s < 3_600 * 2 => format!("{}m", s / 60),
+        s if s < 86_400 * 2 => format!("{}h", s / 3_600),
+        s if s < 86_400 * 365 * 2 => format!("{}d", s / 86_400),
+        s => format!("{}y", s / (86_400 * 365)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PODS: &str = r#"{"items": [
+        {
+            "metadata": {
+                "name": "api-1",
+                "namespace": "default",
+                "creationTimestamp": "2024-01-31T12:00:00Z",
+                "labels": {"app": "api"},
+                "ownerReferences": [
+                    {"kind": "ReplicaSet", "name": "api-7d9f", "controller": true}
+                ]
+            },
+            "spec": {"nodeName": "node-1", "containers": [{"name": "api"}, {"name": "proxy"}]},
+            "status": {
+                "phase": "Running",
+                "podIP": "10.0.0.7",
+                "containerStatuses": [
+                    {
+                        "ready": false,
+                        "restartCount": 7,
+                        "state": {"waiting": {"reason": "CrashLoopBackOff"}}
+                    },
+                    {"ready": true, "restartCount": 1, "state": {"running": {}}}
+                ]
+            }
+        },
+        {
+            "metadata": {"name": "migrate", "deletionTimestamp": "2024-02-01T00:00:00Z"},
+            "spec": {"containers": [{"name": "migrate"}]},
+            "status": {"phase": "Running"}
+        },
+        {
+            "metadata": {"name": "backup"},
+            "spec": {"containers": [{"name": "backup"}]},
+            "status": {
+                "phase": "Succeeded",
+                "containerStatuses": [
+                    {"ready": false, "state": {"terminated": {"reason": "Completed"}}}
+                ]
+            }
+        },
+        {"metadata": {}}
+    ]}"#;
+
+    #[test]
+    fn parses_pods_like_kubectl_get_pods() {
+        let pods = parse_pods(PODS).unwrap();
+        assert_eq!(pods.len(), 3);
+
+        let api = &pods[0];
+        assert_eq!(api.status, "CrashLoopBackOff");
+        assert_eq!(api.ready_display(), "1/2");
+        assert_eq!(api.restarts, 8);
+        assert_eq!(api.created, Some(1_706_702_400));
+        assert_eq!(api.node.as_deref(), Some("node-1"));
+        assert_eq!(api.ip.as_deref(), Some("10.0.0.7"));
+        assert_eq!(api.owner, Some(("ReplicaSet".to_string(), "api-7d9f".to_string())));
+        assert_eq!(api.labels.get("app").map(|v| v.as_str()), Some("api"));
+        assert_eq!(api.key(), "default/api-1");
+
+        assert_eq!(pods[1].status, "Terminating");
+        assert_eq!(pods[1].age_display(), "<unknown>");
+        assert_eq!(pods[2].status, "Succeeded");
+        assert!(parse_pods(r#"{"kind": "Pod"}"#).is_err());
+    }
+
+    #[test]
+    fn parses_rfc3339_timestamps() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_timestamp("2024-01-31T12:00:00Z"), Some(1_706_702_400));
+        assert_eq!(parse_timestamp("2024-01-31T12:00:00.123456Z"), Some(1_706_702_400));
+        assert_eq!(parse_timestamp("2024-03-01T00:00:00Z"), Some(1_709_251_200));
+        assert_eq!(parse_timestamp("2024-01-31T12:00:00"), None);
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn formats_age_in_the_largest_fitting_unit() {
+        let now = now_timestamp();
+        assert_eq!(format_age(now + 60), "0s");
+        assert_eq!(format_age(now - 90 * 60), "90m");
+        assert_eq!(format_age(now - 30 * 3_600), "30h");
+        assert_eq!(format_age(now - 400 * 86_400), "400d");
+        assert_eq!(format_age(now - 3 * 365 * 86_400 - 3_600), "3y");
+    }
+
+    #[test]
+    fn sorts_youngest_first_by_age_and_breaks_ties_by_name() {
+        let mut pods = parse_pods(PODS).unwrap();
+        pods[1].created = Some(1_706_702_500);
+        sort_pods(&mut pods, PodSortColumn::Age, false);
+        let names: Vec<&str> = pods.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["migrate", "api-1", "backup"]);
+
+        sort_pods(&mut pods, PodSortColumn::Restarts, true);
+        assert_eq!(pods[0].name, "api-1");
+    }
+}
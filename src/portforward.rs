@@ -0,0 +1,214 @@
+use std::process::Child;
+
+use crate::process::ChildStream;
+
+/// Upper bound on kept stderr lines per forward.
+const MAX_ERROR_LINES: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ForwardStatus {
+    Starting,
+    Active,
+    Failed,
+    Exited,
+}
+
+impl ForwardStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            ForwardStatus::Starting => "Starting",
+            ForwardStatus::Active => "Active",
+            ForwardStatus::Failed => "Failed",
+            ForwardStatus::Exited => "Exited",
+        }
+    }
+}
+
+enum Output {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// A `kubectl port-forward` child process. The process is killed when the
+/// forward is dropped, so forwards never outlive the app.
+pub struct PortForward {
+    pub namespace: String,
+    /// A `kind/name` reference such as `pod/web-0` or `services/web`.
+    pub target: String,
+    pub local: u16,
+    pub remote: u16,
+    pub status: ForwardStatus,
+    /// What kubectl wrote to stderr, most recent last.
+    pub errors: Vec<String>,
+
+    stream: ChildStream<Output>,
+}
+
+impl PortForward {
+    /// Takes over a child started with `args`, reading its piped output.
+    pub fn new(namespace: &str, target: &str, local: u16, remote: u16, child: Child) -> Self {
+        PortForward {
+            namespace: namespace.to_string(),
+            target: target.to_string(),
+            local,
+            remote,
+            status: ForwardStatus::Starting,
+            errors: Vec::new(),
+            stream: ChildStream::new(child, Output::Stdout, Output::Stderr),
+        }
+    }
+
+    pub fn args(namespace: &str, target: &str, local: u16, remote: u16) -> Vec<String> {
+        vec![
+            "port-forward".to_string(),
+            "-n".to_string(),
+            namespace.to_string(),
+            target.to_string(),
+            format!("{}:{}", local, remote),
+        ]
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.status, ForwardStatus::Starting | ForwardStatus::Active)
+    }
+
+    /// Picks up output since the last call. kubectl closes its output when
+    /// it exits, at which point the exit code decides the final status.
+    pub fn poll(&mut self) {
+        let (lines, exited) = self.stream.poll();
+        for line in lines {
+            match line {
+                Output::Stdout(line) => {
+                    if line.starts_with("Forwarding from") {
+                        self.status = ForwardStatus::Active;
+                    }
+                }
+                Output::Stderr(line) => {
+                    if !line.trim().is_empty() {
+                        self.errors.push(line);
+                        if self.errors.len() > MAX_ERROR_LINES {
+                            self.errors.remove(0);
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(success) = exited {
+            self.status = if success {
+                ForwardStatus::Exited
+            } else {
+                ForwardStatus::Failed
+            };
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.stream.stop();
+    }
+
+    pub fn ports_display(&self) -> String {
+        format!("{}:{}", self.local, self.remote)
+    }
+}
+
+/// Parses `local:remote`, or a single port used for both.
+pub fn parse_ports(input: &str) -> Result<(u16, u16), String> {
+    let port = |value: &str| {
+        value
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|&p| p > 0)
+            .ok_or_else(|| format!("Invalid port: {:?}", value.trim()))
+    };
+    match input.split_once(':') {
+        Some((local, remote)) => Ok((port(local)?, port(remote)?)),
+        None => {
+            let both = port(input)?;
+            Ok((both, both))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn parses_port_pairs_and_single_ports() {
+        assert_eq!(parse_ports("8080:80"), Ok((8080, 80)));
+        assert_eq!(parse_ports(" 5432 "), Ok((5432, 5432)));
+        assert_eq!(parse_ports("8080 : 80"), Ok((8080, 80)));
+        assert_eq!(parse_ports(""), Err("Invalid port: \"\"".to_string()));
+        assert_eq!(parse_ports("0:80"), Err("Invalid port: \"0\"".to_string()));
+        assert_eq!(parse_ports("8080:"), Err("Invalid port: \"\"".to_string()));
+        assert_eq!(parse_ports("70000"), Err("Invalid port: \"70000\"".to_string()));
+        assert_eq!(parse_ports("1:2:3"), Err("Invalid port: \"2:3\"".to_string()));
+    }
+
+    fn forward(lines: Vec<Output>) -> PortForward {
+        let (tx, rx) = mpsc::channel();
+        for line in lines {
+            tx.send(line).unwrap();
+        }
+        PortForward {
+            namespace: "default".to_string(),
+            target: "pod/web-0".to_string(),
+            local: 8080,
+            remote: 80,
+            status: ForwardStatus::Starting,
+            errors: Vec::new(),
+            stream: ChildStream::from_receiver(rx),
+        }
+    }
+
+    #[test]
+    fn kubectl_output_drives_the_status() {
+        let mut pf = forward(vec![
+            Output::Stdout("Forwarding from 127.0.0.1:8080 -> 80".to_string()),
+            Output::Stdout("Forwarding from [::1]:8080 -> 80".to_string()),
+            Output::Stdout("Handling connection for 8080".to_string()),
+        ]);
+        // The sender is gone, so the stream ends after its lines, and without
+        // an exit code to go by the forward counts as failed.
+        pf.poll();
+        assert_eq!(pf.status, ForwardStatus::Failed);
+        assert!(!pf.is_running());
+        assert!(pf.errors.is_empty());
+        assert_eq!(pf.ports_display(), "8080:80");
+
+        let mut pf = forward(vec![
+            Output::Stderr(
+                "error: unable to forward port because pod is not running. \
+                 Current status=Pending"
+                    .to_string(),
+            ),
+            Output::Stderr(String::new()),
+        ]);
+        pf.poll();
+        assert_eq!(
+            pf.errors,
+            vec!["error: unable to forward port because pod is not running. Current status=Pending"]
+        );
+    }
+
+    #[test]
+    fn forwards_become_active_once_kubectl_listens() {
+        let (tx, rx) = mpsc::channel();
+        let mut pf = forward(Vec::new());
+        pf.stream = ChildStream::from_receiver(rx);
+
+        pf.poll();
+        assert_eq!(pf.status, ForwardStatus::Starting);
+        tx.send(Output::Stdout("Forwarding from 127.0.0.1:8080 -> 80".to_string())).unwrap();
+        pf.poll();
+        assert_eq!(pf.status, ForwardStatus::Active);
+        assert!(pf.is_running());
+        assert_eq!(
+            PortForward::args("default", "pod/web-0", 8080, 80),
+            vec!["port-forward", "-n", "default", "pod/web-0", "8080:80"]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    io::{BufRead, BufReader, Read},
+    process::Child,
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread,
+};
+
+/// A long-running child, such as `kubectl logs -f`, whose output lines
+/// arrive through a channel. The process is killed when the stream is
+/// stopped or dropped, so it never outlives the view that started it.
+pub struct ChildStream<T> {
+    child: Option<Child>,
+    receiver: Option<Receiver<T>>,
+}
+
+impl<T: Send + 'static> ChildStream<T> {
+    /// Takes over `child`, reading its piped stdout and stderr into lines
+    /// made by `stdout` and `stderr`.
+    pub fn new(mut child: Child, stdout: fn(String) -> T, stderr: fn(String) -> T) -> Self {
+        let (tx, rx) = mpsc::channel();
+        if let Some(source) = child.stdout.take() {
+            spawn_reader(source, tx.clone(), stdout);
+        }
+        if let Some(source) = child.stderr.take() {
+            spawn_reader(source, tx, stderr);
+        }
+        ChildStream {
+            child: Some(child),
+            receiver: Some(rx),
+        }
+    }
+
+    /// A stream of lines sent to `receiver`, without a process behind it.
+    #[cfg(test)]
+    pub(crate) fn from_receiver(receiver: Receiver<T>) -> Self {
+        ChildStream {
+            child: None,
+            receiver: Some(receiver),
+        }
+    }
+
+    /// Lines received since the last call and, once the child has closed its
+    /// output, whether it exited successfully.
+    pub fn poll(&mut self) -> (Vec<T>, Option<bool>) {
+        let Some(rx) = &self.receiver else {
+            return (Vec::new(), None);
+        };
+        let mut lines = Vec::new();
+        loop {
+            match rx.try_recv() {
+                Ok(line) => lines.push(line),
+                Err(TryRecvError::Empty) => return (lines, None),
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        self.receiver = None;
+        let success = self
+            .child
+            .take()
+            .and_then(|mut child| child.wait().ok())
+            .is_some_and(|status| status.success());
+        (lines, Some(success))
+    }
+
+    /// Kills the child. Returns whether it was still running.
+    pub fn stop(&mut self) -> bool {
+        self.receiver = None;
+        let Some(mut child) = self.child.take() else {
+            return false;
+        };
+        let _ = child.kill();
+        let _ = child.wait();
+        true
+    }
+}
+
+impl<T> Drop for ChildStream<T> {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Forwards each line of `source` to `tx` from a background thread until EOF.
+fn spawn_reader<R, T>(source: R, tx: Sender<T>, wrap: fn(String) -> T)
+where
+    R: Read + Send + 'static,
+    T: Send + 'static,
+{
+    thread::spawn(move || {
+        let mut reader = BufReader::new(source);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf)
+                        .trim_end_matches(['\r', '\n'])
+                        .to_string();
+                    if tx.send(wrap(line)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        process::{Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+
+    fn sh(script: &str) -> Child {
+        Command::new("sh")
+            .args(["-c", script])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap()
+    }
+
+    #[test]
+    fn stream_collects_both_outputs_and_the_exit_status() {
+        let mut stream = ChildStream::new(sh("echo out; echo err >&2; exit 3"), Ok, Err);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut lines = Vec::new();
+        let exited = loop {
+            assert!(Instant::now() < deadline, "child did not exit");
+            let (received, exited) = stream.poll();
+            lines.extend(received);
+            if let Some(success) = exited {
+                break success;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+        lines.sort();
+        assert_eq!(lines, [Ok("out".to_string()), Err("err".to_string())]);
+        assert!(!exited);
+        assert!(!stream.stop());
+    }
+
+    #[test]
+    fn stopping_kills_a_running_child() {
+        let mut stream = ChildStream::new(sh("exec sleep 60"), Ok::<_, String>, Err);
+        assert!(stream.stop());
+        assert_eq!(stream.poll(), (Vec::new(), None));
+    }
+}
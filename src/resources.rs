@@ -0,0 +1,91 @@
+/// A listable kind reported by `kubectl api-resources`, CRDs included.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiResource {
+    /// Fully qualified plural name as kubectl accepts it, e.g. `deployments.apps`.
+    pub name: String,
+    pub namespaced: bool,
+}
+
+impl ApiResource {
+    pub fn display_name(&self) -> String {
+        if self.namespaced {
+            self.name.clone()
+        } else {
+            format!("{} (cluster)", self.name)
+        }
+    }
+
+    /// Whether `kubectl port-forward` accepts instances of this kind.
+    pub fn supports_port_forward(&self) -> bool {
+        matches!(self.name.as_str(), "pods" | "services")
+    }
+}
+
+/// Combines the `-o name` output of the namespaced and cluster-scoped
+/// `kubectl api-resources` queries into one sorted list.
+pub fn parse_api_resources(namespaced: &str, cluster: &str) -> Vec<ApiResource> {
+    let names = |output: &str, namespaced: bool| -> Vec<ApiResource> {
+        output
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|name| ApiResource {
+                name: name.to_string(),
+                namespaced,
+            })
+            .collect()
+    };
+
+    let mut resources = names(namespaced, true);
+    resources.extend(names(cluster, false));
+    resources.sort_by(|a, b| a.name.cmp(&b.name));
+    resources
+}
+
+/// Strips the `kind.group/` prefix from `kubectl get -o name` output.
+pub fn parse_resource_names(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| l.rsplit_once('/').map_or(l, |(_, name)| name).to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_namespaced_and_cluster_kinds_sorted_by_name() {
+        // `kubectl api-resources --verbs=list --namespaced=... -o name`
+        let namespaced = "configmaps\npods\nservices\ndeployments.apps\n\
+                          certificates.cert-manager.io\n";
+        let cluster = "namespaces\nnodes\nclusterroles.rbac.authorization.k8s.io\n\n";
+
+        let resources = parse_api_resources(namespaced, cluster);
+        let names: Vec<String> = resources.iter().map(|r| r.display_name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "certificates.cert-manager.io",
+                "clusterroles.rbac.authorization.k8s.io (cluster)",
+                "configmaps",
+                "deployments.apps",
+                "namespaces (cluster)",
+                "nodes (cluster)",
+                "pods",
+                "services",
+            ]
+        );
+        assert!(resources.iter().any(|r| r.name == "pods" && r.supports_port_forward()));
+        assert!(!resources.iter().any(|r| r.name == "nodes" && r.supports_port_forward()));
+    }
+
+    #[test]
+    fn strips_kind_prefixes_from_names() {
+        let output = "deployment.apps/api\ndeployment.apps/web\n\nnamespace/default\n";
+        assert_eq!(parse_resource_names(output), vec!["api", "web", "default"]);
+        assert!(parse_api_resources("", "").is_empty());
+    }
+}
@@ -0,0 +1,404 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+};
+
+use serde_json::Value;
+
+use crate::pods::{format_age, now_timestamp, parse_timestamp};
+use crate::workloads::parse_items;
+
+const MASK: &str = "••••••••";
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const EXPIRY_WARNING_SECS: i64 = 30 * 86_400;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Secret {
+    pub name: String,
+    /// e.g. `Opaque` or `kubernetes.io/tls`.
+    pub secret_type: String,
+    /// Values by key, base64-encoded as the API returns them.
+    pub data: BTreeMap<String, String>,
+    pub created: Option<i64>,
+}
+
+impl Secret {
+    fn from_json(item: &Value) -> Option<Secret> {
+        let metadata = &item["metadata"];
+        let data = item["data"]
+            .as_object()
+            .map(|data| {
+                data.iter()
+                    .map(|(k, v)| (k.clone(), v.as_str().unwrap_or_default().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Secret {
+            name: metadata["name"].as_str()?.to_string(),
+            secret_type: item["type"].as_str().unwrap_or("Opaque").to_string(),
+            data,
+            created: metadata["creationTimestamp"].as_str().and_then(parse_timestamp),
+        })
+    }
+
+    pub fn age_display(&self) -> String {
+        self.created.map_or_else(|| "<unknown>".to_string(), format_age)
+    }
+
+    pub fn decoded(&self, key: &str) -> Option<Vec<u8>> {
+        decode_base64(self.data.get(key)?)
+    }
+
+    /// The certificate in `tls.crt`, if there is one that parses.
+    pub fn certificate(&self) -> Option<Certificate> {
+        let pem = String::from_utf8(self.decoded("tls.crt")?).ok()?;
+        Certificate::from_pem(&pem)
+    }
+}
+
+/// Parses the output of `kubectl get secrets -o json`.
+pub fn parse_secrets(json: &str) -> Result<Vec<Secret>, Box<dyn Error>> {
+    parse_items(json, Secret::from_json)
+}
+
+pub fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        if c == b'=' {
+            break;
+        }
+        let value = BASE64.iter().position(|b| *b == c)? as u32;
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// The parts of an X.509 certificate worth showing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Certificate {
+    pub subject: String,
+    pub issuer: String,
+    /// End of validity as seconds since the Unix epoch.
+    pub not_after: Option<i64>,
+}
+
+impl Certificate {
+    /// Reads the first certificate of a PEM bundle.
+    pub fn from_pem(pem: &str) -> Option<Certificate> {
+        let body = pem.split("-----BEGIN CERTIFICATE-----").nth(1)?;
+        let body = body.split("-----END CERTIFICATE-----").next()?;
+        Certificate::from_der(&decode_base64(body)?)
+    }
+
+    fn from_der(der: &[u8]) -> Option<Certificate> {
+        let (_, certificate) = Der(der).next()?;
+        let (_, tbs) = Der(certificate).next()?;
+        let mut fields = Der(tbs);
+        // The version is optional, then comes the serial number.
+        if fields.next()?.0 == 0xa0 {
+            fields.next()?;
+        }
+        fields.next()?; // signature algorithm
+        let (_, issuer) = fields.next()?;
+        let (_, validity) = fields.next()?;
+        let (_, subject) = fields.next()?;
+
+        let mut validity = Der(validity);
+        validity.next()?;
+        let (tag, not_after) = validity.next()?;
+        Some(Certificate {
+            subject: name_display(subject),
+            issuer: name_display(issuer),
+            not_after: parse_time(tag, not_after),
+        })
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.not_after.is_some_and(|t| t <= now_timestamp())
+    }
+
+    pub fn expires_soon(&self) -> bool {
+        self.not_after
+            .is_some_and(|t| t - now_timestamp() < EXPIRY_WARNING_SECS)
+    }
+
+    pub fn expiry_display(&self) -> String {
+        let Some(not_after) = self.not_after else {
+            return "<unknown>".to_string();
+        };
+        let remaining = not_after - now_timestamp();
+        match remaining {
+            r if r <= 0 => format!("expired {} ago", format_age(not_after)),
+            r if r < 86_400 * 2 => format!("in {}h", r / 3_600),
+            r => format!("in {}d", r / 86_400),
+        }
+    }
+}
+
+/// Reads DER-encoded ASN.1 one element at a time.
+struct Der<'a>(&'a [u8]);
+
+impl<'a> Der<'a> {
+    /// The tag and contents of the next element.
+    fn next(&mut self) -> Option<(u8, &'a [u8])> {
+        let (&tag, rest) = self.0.split_first()?;
+        let (&first, mut rest) = rest.split_first()?;
+        let len = if first < 0x80 {
+            first as usize
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 || rest.len() < count {
+                return None;
+            }
+            let len = rest[..count].iter().fold(0, |len, b| len << 8 | *b as usize);
+            rest = &rest[count..];
+            len
+        };
+        if rest.len() < len {
+            return None;
+        }
+        let (contents, rest) = rest.split_at(len);
+        self.0 = rest;
+        Some((tag, contents))
+    }
+}
+
+/// Formats a distinguished name like `openssl x509 -subject`, e.g.
+/// `O=Example, CN=web.example.com`.
+fn name_display(name: &[u8]) -> String {
+    let mut parts = Vec::new();
+    let mut names = Der(name);
+    while let Some((_, set)) = names.next() {
+        let mut attributes = Der(set);
+        while let Some((_, attribute)) = attributes.next() {
+            let mut fields = Der(attribute);
+            let (Some((_, oid)), Some((_, value))) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let label = match oid {
+                [0x55, 0x04, 0x03] => "CN",
+                [0x55, 0x04, 0x06] => "C",
+                [0x55, 0x04, 0x07] => "L",
+                [0x55, 0x04, 0x08] => "ST",
+                [0x55, 0x04, 0x0a] => "O",
+                [0x55, 0x04, 0x0b] => "OU",
+                _ => continue,
+            };
+            parts.push(format!("{}={}", label, String::from_utf8_lossy(value)));
+        }
+    }
+    parts.join(", ")
+}
+
+/// Parses a UTCTime or GeneralizedTime into seconds since the Unix epoch.
+fn parse_time(tag: u8, value: &[u8]) -> Option<i64> {
+    let text = std::str::from_utf8(value).ok()?;
+    let (year, rest) = match tag {
+        0x17 => {
+            let year: i64 = text.get(..2)?.parse().ok()?;
+            (if year < 50 { 2000 + year } else { 1900 + year }, text.get(2..)?)
+        }
+        0x18 => (text.get(..4)?.parse().ok()?, text.get(4..)?),
+        _ => return None,
+    };
+    let part = |i: usize| rest.get(i..i + 2);
+    parse_timestamp(&format!(
+        "{}-{}-{}T{}:{}:{}Z",
+        year,
+        part(0)?,
+        part(2)?,
+        part(4)?,
+        part(6)?,
+        part(8)?
+    ))
+}
+
+/// A secret on screen, with its values masked until revealed key by key.
+pub struct SecretView {
+    pub secret: Secret,
+    pub selected: usize,
+    /// Keys whose decoded values are shown.
+    pub revealed: BTreeSet<String>,
+    pub certificate: Option<Certificate>,
+}
+
+impl SecretView {
+    pub fn new(secret: Secret) -> Self {
+        SecretView {
+            certificate: secret.certificate(),
+            secret,
+            selected: 0,
+            revealed: BTreeSet::new(),
+        }
+    }
+
+    pub fn selected_key(&self) -> Option<&str> {
+        self.secret.data.keys().nth(self.selected).map(|k| k.as_str())
+    }
+
+    pub fn select_previous(&mut self) {
+        let last = self.secret.data.len().saturating_sub(1);
+        self.selected = if self.selected > 0 { self.selected - 1 } else { last };
+    }
+
+    pub fn select_next(&mut self) {
+        let last = self.secret.data.len().saturating_sub(1);
+        self.selected = if self.selected < last { self.selected + 1 } else { 0 };
+    }
+
+    pub fn toggle_reveal(&mut self) {
+        let Some(key) = self.selected_key().map(|k| k.to_string()) else {
+            return;
+        };
+        if !self.revealed.remove(&key) {
+            self.revealed.insert(key);
+        }
+    }
+
+    /// The decoded value of `key`, as text where it is valid UTF-8.
+    pub fn value(&self, key: &str) -> String {
+        match self.secret.decoded(key) {
+            None => "<invalid base64>".to_string(),
+            Some(bytes) => String::from_utf8(bytes)
+                .unwrap_or_else(|e| format!("<{} bytes of binary data>", e.as_bytes().len())),
+        }
+    }
+
+    /// The value of `key` as shown: masked with its size unless revealed.
+    pub fn display_value(&self, key: &str) -> String {
+        if self.revealed.contains(key) {
+            return self.value(key);
+        }
+        match self.secret.decoded(key) {
+            Some(bytes) => format!("{} ({} bytes)", MASK, bytes.len()),
+            None => MASK.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A DER element, with a long-form length where one is needed.
+    fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut der = vec![tag];
+        match contents.len() {
+            len if len < 0x80 => der.push(len as u8),
+            len if len <= 0xff => der.extend([0x81, len as u8]),
+            len => der.extend([0x82, (len >> 8) as u8, len as u8]),
+        }
+        der.extend(contents);
+        der
+    }
+
+    fn name(common_name: &str) -> Vec<u8> {
+        let attribute = [tlv(0x06, &[0x55, 0x04, 0x03]), tlv(0x0c, common_name.as_bytes())];
+        tlv(0x30, &tlv(0x31, &tlv(0x30, &attribute.concat())))
+    }
+
+    /// A certificate with just the fields `Certificate` reads, padded so
+    /// that its outer lengths take the long form.
+    fn certificate(not_after: &[u8]) -> Vec<u8> {
+        let validity = [tlv(0x17, b"240101000000Z"), not_after.to_vec()].concat();
+        let tbs = [
+            tlv(0xa0, &tlv(0x02, &[2])),
+            tlv(0x02, &[1]),
+            tlv(0x30, &tlv(0x06, &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02])),
+            name("ca.example.com"),
+            tlv(0x30, &validity),
+            name("web.example.com"),
+            tlv(0x30, &[0; 200]),
+        ]
+        .concat();
+        tlv(0x30, &[tlv(0x30, &tbs), tlv(0x03, &[0; 64])].concat())
+    }
+
+    #[test]
+    fn base64_skips_whitespace_and_stops_at_padding() {
+        assert_eq!(decode_base64("aGVs\nbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64(" aGk= ").unwrap(), b"hi");
+        assert_eq!(decode_base64("aGk").unwrap(), b"hi");
+        assert_eq!(decode_base64("").unwrap(), b"");
+    }
+
+    #[test]
+    fn base64_rejects_invalid_characters() {
+        assert_eq!(decode_base64("aGk*"), None);
+        assert_eq!(decode_base64("a-_b"), None);
+        assert_eq!(decode_base64("aGVsbG8é"), None);
+    }
+
+    #[test]
+    fn base64_round_trips() {
+        assert_eq!(encode_base64(b"hi"), "aGk=");
+        assert_eq!(encode_base64(b"hello"), "aGVsbG8=");
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..8 {
+            assert_eq!(decode_base64(&encode_base64(&bytes[..len])).unwrap(), &bytes[..len]);
+        }
+        assert_eq!(decode_base64(&encode_base64(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn der_reads_long_form_lengths() {
+        let der = [tlv(0x04, &[7; 0x80]), tlv(0x04, &[8; 0x100]), tlv(0x05, &[])].concat();
+        let mut elements = Der(&der);
+        assert_eq!(elements.next(), Some((0x04, &[7; 0x80][..])));
+        assert_eq!(elements.next(), Some((0x04, &[8; 0x100][..])));
+        assert_eq!(elements.next(), Some((0x05, &[][..])));
+        assert_eq!(elements.next(), None);
+
+        // An indefinite length, and one past the end of the input.
+        assert_eq!(Der(&[0x30, 0x80, 0x00, 0x00]).next(), None);
+        assert_eq!(Der(&[0x04, 0x82, 0x01, 0x00, 0x00]).next(), None);
+    }
+
+    #[test]
+    fn certificate_fields_are_read_and_truncation_is_rejected() {
+        let der = certificate(&tlv(0x18, b"20500101000000Z"));
+        let cert = Certificate::from_der(&der).unwrap();
+        assert_eq!(cert.subject, "CN=web.example.com");
+        assert_eq!(cert.issuer, "CN=ca.example.com");
+        assert_eq!(cert.not_after, Some(2_524_608_000));
+
+        for len in 0..der.len() {
+            assert_eq!(Certificate::from_der(&der[..len]), None, "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn utc_time_years_pivot_at_fifty() {
+        assert_eq!(parse_time(0x17, b"491231235959Z"), Some(2_524_607_999));
+        assert_eq!(parse_time(0x17, b"500101000000Z"), Some(-631_152_000));
+        assert_eq!(parse_time(0x18, b"20500101000000Z"), Some(2_524_608_000));
+        assert_eq!(parse_time(0x18, b"19500101000000Z"), Some(-631_152_000));
+        assert_eq!(parse_time(0x17, b"5001"), None);
+        assert_eq!(parse_time(0x04, b"500101000000Z"), None);
+    }
+}
@@ -1,193 +1,158 @@
-use std::error::Error;
+use std::{collections::BTreeSet, error::Error, io, time::Duration};
 
-use crossterm::event::{self, Event, KeyCode};
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{
+        Block, Borders, Cell, Clear, Gauge, List, ListItem, ListState, Paragraph, Row, Table,
+        TableState, Wrap,
+    },
     Frame, Terminal,
 };
 
-use crate::app::{App, AppState};
-use tui::widgets::ListState;
+use crate::app::{App, AppState, ContainerAction, Shell};
+use crate::debug::CopyField;
+use crate::delete::DeleteField;
+use crate::filter;
+use crate::metrics::SPARKLINE_WIDTH;
+use crate::nodes::{DrainField, DrainStatus};
+use crate::portforward::ForwardStatus;
+use crate::pods::{Pod, PodSortColumn};
+use crate::watch::RowChange;
+use crate::worker;
+use crate::workloads::WorkloadKind;
 
 pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result<(), Box<dyn Error>> {
     loop {
+        app.tick = app.tick.wrapping_add(1);
+        app.poll_jobs();
+        app.poll_rollout();
+        app.poll_watch();
+        app.poll_port_forwards();
+        app.poll_drain();
+        if let Some(logs) = app.logs.as_mut() {
+            logs.poll();
+        }
+
         terminal.draw(|f| ui(f, &mut app))?;
 
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+
         if let Event::Key(key) = event::read()? {
-            if key.code == KeyCode::Char('q') {
+            if key.code == KeyCode::Char('q') && !app.is_text_input() {
                 return Ok(());
             }
 
-            match app.state {
-                AppState::MainMenu => handle_main_menu(&mut app, key.code),
-                AppState::NamespaceSelection => handle_namespace_selection(&mut app, key.code),
-                AppState::ContextSelection => handle_context_selection(&mut app, key.code),
-                AppState::ExecPodSelection => handle_exec_pod_selection(&mut app, key.code),
-                AppState::PodSelection => handle_copy_pod_selection(&mut app, key.code),
-                AppState::CopyPodNameInput => handle_copy_pod_name(&mut app, key.code),
-                AppState::Message | AppState::ShowOutput => {
-                    app.state = AppState::MainMenu;
-                }
+            handle_key(&mut app, key.code);
+
+            if let Some(session) = app.interactive.take() {
+                suspend_terminal(terminal)?;
+                app.run_interactive(session);
+                resume_terminal(terminal)?;
             }
         }
     }
 }
 
-fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    let vertical_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Min(3),
-            Constraint::Length(3),
-        ])
-        .split(f.size());
-
-    let header = Paragraph::new(format!(
-        " Context: {} | Namespace: {} ",
-        app.selected_context.as_deref().unwrap_or("None"),
-        app.current_namespace()
-    ))
-    .block(Block::default().borders(Borders::BOTTOM));
-    f.render_widget(header, vertical_chunks[0]);
+/// Hands the terminal back to the shell so an interactive child can use it.
+fn suspend_terminal<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
 
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
-        .split(vertical_chunks[1]);
+/// Re-enters the alternate screen and forces a full redraw on the next frame.
+fn resume_terminal<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.hide_cursor()?;
+    terminal.clear()?;
+    Ok(())
+}
 
-    let command_items: Vec<ListItem> = app
-        .commands
-        .iter()
-        .map(|c| ListItem::new(c.as_str()))
-        .collect();
+pub fn handle_key(app: &mut App, key_code: KeyCode) {
+    app.status_message = None;
 
-    let commands = tui::widgets::List::new(command_items)
-        .block(Block::default().borders(Borders::ALL).title("Commands"))
-        .highlight_symbol("▶");
-    f.render_stateful_widget(commands, main_chunks[0], &mut app.list_state);
+    if app.is_filterable() && handle_filter_key(app, key_code) {
+        return;
+    }
 
     match app.state {
-        AppState::MainMenu => {
-            render_output_preview(f, app, main_chunks[1]);
+        AppState::MainMenu => handle_main_menu(app, key_code),
+        AppState::NamespaceSelection => handle_namespace_selection(app, key_code),
+        AppState::ContextSelection => handle_context_selection(app, key_code),
+        AppState::ExecPodSelection => handle_exec_pod_selection(app, key_code),
+        AppState::PodSelection => handle_copy_pod_selection(app, key_code),
+        AppState::CopyPodForm => handle_copy_pod_form(app, key_code),
+        AppState::LogContainerSelection => handle_log_container_selection(app, key_code),
+        AppState::PodLogs => handle_pod_logs(app, key_code),
+        AppState::ExecContainerSelection => handle_exec_container_selection(app, key_code),
+        AppState::ShellSelection => handle_shell_selection(app, key_code),
+        AppState::ExecCommandInput => handle_exec_command_input(app, key_code),
+        AppState::DebugPodSelection => handle_debug_pod_selection(app, key_code),
+        AppState::DebugContainerSelection => handle_debug_container_selection(app, key_code),
+        AppState::DebugImageSelection => handle_debug_image_selection(app, key_code),
+        AppState::Describe => handle_describe(app, key_code),
+        AppState::Manifest => handle_manifest(app, key_code),
+        AppState::EditConfirm => handle_edit_confirm(app, key_code),
+        // The apply cannot be cancelled; its outcome replaces this screen.
+        AppState::Applying => {}
+        AppState::ResourceKindSelection => handle_resource_kind_selection(app, key_code),
+        AppState::ResourceList => handle_resource_list(app, key_code),
+        AppState::ConfirmDelete => handle_confirm_delete(app, key_code),
+        AppState::DeploymentList => handle_deployment_list(app, key_code),
+        AppState::ScaleInput => handle_scale_input(app, key_code),
+        AppState::RevisionSelection => handle_revision_selection(app, key_code),
+        AppState::RolloutStatus => handle_rollout_status(app, key_code),
+        AppState::StatefulSetList => handle_workload_list(app, WorkloadKind::StatefulSet, key_code),
+        AppState::DaemonSetList => handle_workload_list(app, WorkloadKind::DaemonSet, key_code),
+        AppState::JobList => handle_workload_list(app, WorkloadKind::Job, key_code),
+        AppState::CronJobList => handle_workload_list(app, WorkloadKind::CronJob, key_code),
+        AppState::PortForwardInput => handle_port_forward_input(app, key_code),
+        AppState::PortForwardList => handle_port_forward_list(app, key_code),
+        AppState::BulkSummary => app.close_bulk_summary(),
+        AppState::EventList => handle_event_list(app, key_code),
+        AppState::NodeList => handle_node_list(app, key_code),
+        AppState::NodePodList => handle_exec_pod_selection(app, key_code),
+        AppState::DrainOptions => handle_drain_options(app, key_code),
+        AppState::DrainProgress => handle_drain_progress(app, key_code),
+        AppState::SecretList => handle_secret_list(app, key_code),
+        AppState::SecretDetail => handle_secret_detail(app, key_code),
+        AppState::Message | AppState::ShowOutput => {
+            app.state = AppState::MainMenu;
         }
-        AppState::NamespaceSelection => render_list_panel(
-            f,
-            main_chunks[1],
-            &app.namespaces,
-            &mut app.namespace_list_state,
-            "Select Namespace",
-        ),
-        AppState::ContextSelection => render_list_panel(
-            f,
-            main_chunks[1],
-            &app.contexts,
-            &mut app.context_list_state,
-            "Select Context",
-        ),
-        AppState::ExecPodSelection => render_list_panel(
-            f,
-            main_chunks[1],
-            &app.pods,
-            &mut app.pod_list_state,
-            "Select Pod to Exec",
-        ),
-        AppState::PodSelection => render_list_panel(
-            f,
-            main_chunks[1],
-            &app.pods,
-            &mut app.pod_list_state,
-            "Select Pod to Copy",
-        ),
-        AppState::CopyPodNameInput => render_copy_pod_ui(f, app, main_chunks[1]),
-        AppState::ShowOutput => render_output_panel(f, app, main_chunks[1]),
-        AppState::Message => render_message_panel(f, app, main_chunks[1]),
     }
-
-    let status = match app.state {
-        AppState::NamespaceSelection
-        | AppState::ContextSelection
-        | AppState::ExecPodSelection
-        | AppState::PodSelection => "[↑/↓ or j/k] Navigate  [Enter/Right] Select  [Esc] Back  [q] Quit",
-        AppState::CopyPodNameInput => "[Enter] Submit  [Esc] Back  [q] Quit",
-        AppState::Message | AppState::ShowOutput => "Press any key to return to main menu, or [q] Quit",
-        AppState::MainMenu => "[↑/↓ or j/k] Navigate  [Enter/Right] Select  [q] Quit",
-    };
-    let status_bar = Paragraph::new(status).block(Block::default().borders(Borders::TOP));
-    f.render_widget(status_bar, vertical_chunks[2]);
 }
 
-fn render_output_preview<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
-    let selected = app.list_state.selected().unwrap_or(0);
-    if selected == 2 && !app.output.is_empty() {
-        let output = Paragraph::new(app.output.as_str())
-            .wrap(tui::widgets::Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL).title("Pods Preview"));
-        f.render_widget(output, area);
-    } else {
-        render_default_panel(f, area);
+/// Keys for the `/` filter of a list. Returns false for keys the list
+/// itself should handle, such as navigation while typing.
+fn handle_filter_key(app: &mut App, key_code: KeyCode) -> bool {
+    if app.is_filtering() {
+        match key_code {
+            KeyCode::Char(c) => app.filter_push(c),
+            KeyCode::Backspace => app.filter_pop(),
+            KeyCode::Enter => app.finish_filter(),
+            KeyCode::Esc => app.clear_filter(),
+            _ => return false,
+        }
+        return true;
     }
-}
-
-fn render_list_panel<B: Backend>(
-    f: &mut Frame<B>,
-    area: Rect,
-    items: &[String],
-    state: &mut ListState,
-    title: &str,
-) {
-    let list_items: Vec<ListItem> = items.iter().map(|i| ListItem::new(i.as_str())).collect();
-    let list = tui::widgets::List::new(list_items)
-        .block(Block::default().borders(Borders::ALL).title(title))
-        .highlight_symbol("▶");
-    f.render_stateful_widget(list, area, state);
-}
-
-fn render_copy_pod_ui<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    use tui::layout::{Constraint, Direction, Layout};
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(3), Constraint::Length(3)])
-        .split(area);
-
-    let info = Paragraph::new(format!(
-        "Copying pod: {}\nEnter new pod name:",
-        app.selected_pod.as_deref().unwrap_or("None")
-    ))
-    .block(Block::default().borders(Borders::ALL));
-    f.render_widget(info, chunks[0]);
-
-    let input = Paragraph::new(app.new_pod_name.as_str())
-        .block(Block::default().borders(Borders::ALL).title("New Pod Name"));
-    f.render_widget(input, chunks[1]);
-
-    f.set_cursor(
-        chunks[1].x + app.new_pod_name.len() as u16 + 1,
-        chunks[1].y + 1,
-    );
-}
-
-fn render_output_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let output = Paragraph::new(app.output.as_str())
-        .wrap(tui::widgets::Wrap { trim: true })
-        .block(Block::default().borders(Borders::ALL).title("Output"));
-    f.render_widget(output, area);
-}
-
-fn render_message_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let message = Paragraph::new(app.message.as_str())
-        .wrap(tui::widgets::Wrap { trim: true })
-        .block(Block::default().borders(Borders::ALL).title("Message"));
-    f.render_widget(message, area);
-}
-
-fn render_default_panel<B: Backend>(f: &mut Frame<B>, area: Rect) {
-    let block = Block::default().borders(Borders::ALL).title("Welcome");
-    f.render_widget(block, area);
+    match key_code {
+        KeyCode::Char('/') => app.open_filter(),
+        KeyCode::Esc if app.filter.applies_to(app.state) => app.clear_filter(),
+        _ => return false,
+    }
+    true
 }
 
 fn handle_main_menu(app: &mut App, key_code: KeyCode) {
@@ -206,27 +171,23 @@ fn handle_main_menu(app: &mut App, key_code: KeyCode) {
             maybe_load_preview(app, new_idx);
         }
         KeyCode::Right | KeyCode::Enter => match old_index {
-            0 => handle_load_contexts(app),
-            1 => handle_load_namespaces(app),
-            2 => {
-                if let Err(e) = app.load_pods() {
-                    app.message = format!("Error loading pods: {}", e);
-                    app.state = AppState::Message;
-                } else {
-                    app.state = AppState::ExecPodSelection;
-                }
-            }
-            3 => {
-                match app.load_pods() {
-                    Ok(_) => app.state = AppState::PodSelection,
-                    Err(e) => {
-                        app.message = format!("Error loading pods: {}", e);
-                        app.state = AppState::Message;
-                    }
-                }
-            }
+            0 => app.load_contexts(),
+            1 => app.load_namespaces(),
+            2 => app.load_pods(AppState::ExecPodSelection),
+            3 => app.load_pods(AppState::PodSelection),
+            4 => app.load_pods(AppState::DebugPodSelection),
+            5 => app.load_api_resources(),
+            6 => app.load_deployments(),
+            7..=10 => app.load_workloads(WorkloadKind::ALL[old_index - 7]),
+            11 => app.open_port_forwards(),
+            12 => app.open_events(None),
+            13 => app.load_nodes(),
+            14 => app.load_secrets(),
             _ => {}
         },
+        KeyCode::Esc => {
+            app.worker.cancel_all();
+        }
         _ => {}
     }
 }
@@ -240,36 +201,17 @@ fn maybe_load_preview(app: &mut App, new_idx: usize) {
 
     if new_idx == 2 {
         let namespace = app.current_namespace();
-        let res = app.execute_kubectl(&["get", "pods", "-n", &namespace]);
-        if let Err(e) = res {
-            app.output = format!("Error listing pods: {}", e);
-        }
+        app.execute_kubectl(&["get", "pods", "-n", &namespace]);
+    } else if new_idx == 12 {
+        app.preview_warnings();
     }
 
     app.last_main_menu_index = Some(new_idx);
 }
 
-fn handle_load_namespaces(app: &mut App) {
-    if let Err(e) = app.load_namespaces() {
-        app.message = format!("Error loading namespaces: {}", e);
-        app.state = AppState::Message;
-    } else {
-        app.state = AppState::NamespaceSelection;
-    }
-}
-
-fn handle_load_contexts(app: &mut App) {
-    if let Err(e) = app.load_contexts() {
-        app.message = format!("Error loading contexts: {}", e);
-        app.state = AppState::Message;
-    } else {
-        app.state = AppState::ContextSelection;
-    }
-}
-
 fn handle_exec_pod_selection(app: &mut App, key_code: KeyCode) {
     let selected = app.pod_list_state.selected().unwrap_or(0);
-    let last_idx = app.pods.len().saturating_sub(1);
+    let last_idx = app.visible_indices().len().saturating_sub(1);
 
     match key_code {
         KeyCode::Up | KeyCode::Char('k') => {
@@ -283,140 +225,3931 @@ fn handle_exec_pod_selection(app: &mut App, key_code: KeyCode) {
             }));
         }
         KeyCode::Enter => {
-            let pod = app.pods.get(selected).cloned();
-            if let Some(chosen_pod) = pod {
-                if let Err(e) = app.exec_pod(&chosen_pod) {
-                    app.message = format!("Error exec into pod: {}", e);
+            if let Some(chosen_pod) = app.selected_pod_name() {
+                app.load_containers(&chosen_pod, ContainerAction::Exec);
+            }
+        }
+        KeyCode::Char(' ') => {
+            app.toggle_mark();
+            app.pod_list_state.select(Some((selected + 1).min(last_idx)));
+        }
+        KeyCode::Char('a') => app.mark_all(),
+        KeyCode::Char('i') => app.invert_marks(),
+        KeyCode::Char('l') if !app.marked.is_empty() => app.fetch_logs(),
+        KeyCode::Char('l') => {
+            if let Some(chosen_pod) = app.selected_pod_name() {
+                app.load_containers(&chosen_pod, ContainerAction::Logs);
+            }
+        }
+        KeyCode::Char('f') => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.open_port_forward(app.pod_namespace(&pod), &format!("pod/{}", pod));
+            }
+        }
+        KeyCode::Char('d') => {
+            let pods = app.targets();
+            if !pods.is_empty() {
+                app.describe_pods(&pods);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.view_pod_manifest(&pod);
+            }
+        }
+        KeyCode::Char('e') => app.open_pod_events(),
+        KeyCode::Char('x') | KeyCode::Delete => {
+            let pods = app.targets();
+            if !pods.is_empty() {
+                app.confirm_delete_all("pod", pods, true);
+            }
+        }
+        KeyCode::Char('r') => app.restart_owners(),
+        KeyCode::Char('s') => app.sort_pods(app.pod_sort.next(), false),
+        KeyCode::Char('S') => app.sort_pods(app.pod_sort, !app.pod_sort_descending),
+        KeyCode::Esc if !app.marked.is_empty() => app.marked.clear(),
+        KeyCode::Esc => {
+            // The first Esc cancels a pending container lookup.
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = match app.state {
+                    AppState::NodePodList => AppState::NodeList,
+                    _ => AppState::MainMenu,
+                };
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_log_container_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.container_list_state.selected().unwrap_or(0);
+    let last_idx = app.containers.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.container_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.container_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => {
+            let container = app.selected_container_name();
+            if let (Some(pod), Some(container)) = (app.selected_pod.clone(), container) {
+                if let Err(e) = app.open_logs(&pod, Some(&container)) {
+                    app.message = format!("Error streaming logs: {}", e);
                     app.state = AppState::Message;
                 }
             }
         }
-        KeyCode::Esc => {
-            app.state = AppState::MainMenu;
+        KeyCode::Esc => app.state = app.pod_return,
+        _ => {}
+    }
+}
+
+fn handle_exec_container_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.container_list_state.selected().unwrap_or(0);
+    let last_idx = app.containers.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.container_list_state
+                .select(Some(selected.saturating_sub(1)))
         }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.container_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => {
+            if let Some(container) = app.selected_container_name() {
+                app.selected_container = Some(container);
+                app.open_shell_selection();
+            }
+        }
+        KeyCode::Esc => app.state = app.pod_return,
         _ => {}
     }
 }
 
-fn handle_copy_pod_selection(app: &mut App, key_code: KeyCode) {
-    let selected = app.pod_list_state.selected().unwrap_or(0);
-    let last_idx = app.pods.len().saturating_sub(1);
+fn handle_shell_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.shell_list_state.selected().unwrap_or(0);
+    let last_idx = Shell::ALL.len() - 1;
 
     match key_code {
         KeyCode::Up | KeyCode::Char('k') => {
-            app.pod_list_state.select(Some(selected.saturating_sub(1)));
+            app.shell_list_state.select(Some(selected.saturating_sub(1)))
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.pod_list_state.select(Some(if selected < last_idx {
+            app.shell_list_state.select(Some(if selected < last_idx {
                 selected + 1
             } else {
                 0
             }));
         }
         KeyCode::Enter => {
-            let pod = app.pods.get(selected).cloned();
-            if let Some(cloned_pod) = pod {
-                app.selected_pod = Some(cloned_pod);
-                app.new_pod_name.clear();
-                app.state = AppState::CopyPodNameInput;
+            let started = app.exec_with_shell(Shell::ALL[selected]);
+            if !started {
+                app.state = AppState::ExecCommandInput;
             }
         }
         KeyCode::Esc => {
-            app.state = AppState::MainMenu;
+            app.state = if app.containers.len() > 1 {
+                AppState::ExecContainerSelection
+            } else {
+                app.pod_return
+            };
         }
         _ => {}
     }
 }
 
-fn handle_copy_pod_name(app: &mut App, key_code: KeyCode) {
+fn handle_exec_command_input(app: &mut App, key_code: KeyCode) {
     match key_code {
         KeyCode::Enter => {
-            if app.new_pod_name.is_empty() {
-                app.message = "Please enter a new pod name".to_string();
-                app.state = AppState::Message;
-            } else if let Some(op) = app.selected_pod.clone() {
-                let new_name = app.new_pod_name.clone();
-                let result = app.copy_pod(&op, &new_name);
-
-                if let Err(e) = result {
-                    app.message = format!("Error copying pod: {}", e);
-                    app.state = AppState::Message;
-                }
-                app.selected_pod = None;
-                app.new_pod_name.clear();
+            let started = app.exec_with_shell(Shell::Custom);
+            if !started {
+                app.status_message = Some("Please enter a command".to_string());
             }
         }
         KeyCode::Char(c) => {
-            app.new_pod_name.push(c);
+            app.exec_command.push(c);
         }
         KeyCode::Backspace => {
-            app.new_pod_name.pop();
+            app.exec_command.pop();
         }
         KeyCode::Esc => {
-            app.state = AppState::PodSelection;
+            app.state = AppState::ShellSelection;
         }
         _ => {}
     }
 }
 
-fn handle_namespace_selection(app: &mut App, key_code: KeyCode) {
-    let selected = app.namespace_list_state.selected().unwrap_or(0);
-    let last_idx = app.namespaces.len().saturating_sub(1);
+fn handle_pod_logs(app: &mut App, key_code: KeyCode) {
+    let Some(logs) = app.logs.as_mut() else {
+        app.state = app.pod_return;
+        return;
+    };
+    let page = logs.page_height.max(1);
 
-    match key_code {
+    let result = match key_code {
         KeyCode::Up | KeyCode::Char('k') => {
-            app.namespace_list_state.select(Some(selected.saturating_sub(1)));
+            logs.scroll_up(1);
+            Ok(())
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.namespace_list_state.select(Some(if selected < last_idx {
-                selected + 1
-            } else {
-                0
-            }));
+            logs.scroll_down(1);
+            Ok(())
         }
-        KeyCode::Enter => {
-            let ns = app.namespaces.get(selected).cloned();
-            if let Some(ns) = ns {
-                app.selected_namespace = Some(ns);
-                app.state = AppState::MainMenu;
-            }
+        KeyCode::PageUp => {
+            logs.scroll_up(page);
+            Ok(())
         }
+        KeyCode::PageDown => {
+            logs.scroll_down(page);
+            Ok(())
+        }
+        KeyCode::Home => {
+            logs.scroll_top();
+            Ok(())
+        }
+        KeyCode::End => {
+            logs.scroll_bottom();
+            Ok(())
+        }
+        KeyCode::Char('f') => {
+            logs.toggle_follow();
+            Ok(())
+        }
+        KeyCode::Char('p') => logs.toggle_previous(),
+        KeyCode::Char('t') => logs.toggle_timestamps(),
         KeyCode::Esc => {
-            app.state = AppState::MainMenu;
+            app.close_logs();
+            Ok(())
         }
+        _ => Ok(()),
+    };
+
+    if let Err(e) = result {
+        app.logs = None;
+        app.message = format!("Error streaming logs: {}", e);
+        app.state = AppState::Message;
+    }
+}
+
+fn handle_describe(app: &mut App, key_code: KeyCode) {
+    let Some(view) = app.describe.as_mut() else {
+        app.state = app.detail_return;
+        return;
+    };
+    let page = view.page_height.max(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => view.cursor_up(1),
+        KeyCode::Down | KeyCode::Char('j') => view.cursor_down(1),
+        KeyCode::PageUp => view.cursor_up(page),
+        KeyCode::PageDown => view.cursor_down(page),
+        KeyCode::Home => view.cursor_top(),
+        KeyCode::End => view.cursor_bottom(),
+        KeyCode::Enter | KeyCode::Char(' ') => view.toggle_fold(),
+        KeyCode::Char('z') => view.toggle_fold_all(),
+        KeyCode::Esc => app.close_describe(),
         _ => {}
     }
 }
 
-fn handle_context_selection(app: &mut App, key_code: KeyCode) {
-    let selected = app.context_list_state.selected().unwrap_or(0);
-    let last_idx = app.contexts.len().saturating_sub(1);
+fn handle_manifest(app: &mut App, key_code: KeyCode) {
+    let Some(view) = app.manifest.as_mut() else {
+        app.state = app.detail_return;
+        return;
+    };
+    let page = view.page_height.max(1);
+
+    if view.searching {
+        match key_code {
+            KeyCode::Enter => view.finish_search(),
+            KeyCode::Esc => view.cancel_search(),
+            KeyCode::Char(c) => view.search.push(c),
+            KeyCode::Backspace => {
+                view.search.pop();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+        KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+        KeyCode::PageUp => view.scroll_up(page),
+        KeyCode::PageDown => view.scroll_down(page),
+        KeyCode::Home => view.scroll_top(),
+        KeyCode::End => view.scroll_bottom(),
+        KeyCode::Left => view.scroll_left(),
+        KeyCode::Right => view.scroll_right(),
+        KeyCode::Char('/') => view.start_search(),
+        KeyCode::Char('n') => view.next_match(),
+        KeyCode::Char('N') => view.previous_match(),
+        KeyCode::Char('h') => view.toggle_hide_noise(),
+        KeyCode::Char('e') => app.edit_manifest(),
+        KeyCode::Esc => app.close_manifest(),
+        _ => {}
+    }
+}
+
+fn handle_edit_confirm(app: &mut App, key_code: KeyCode) {
+    let Some(pending) = app.pending_edit.as_mut() else {
+        return;
+    };
+    let page = pending.page_height.max(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => pending.scroll_up(1),
+        KeyCode::Down | KeyCode::Char('j') => pending.scroll_down(1),
+        KeyCode::PageUp => pending.scroll_up(page),
+        KeyCode::PageDown => pending.scroll_down(page),
+        KeyCode::Char('y') => app.apply_edit(),
+        KeyCode::Char('n') | KeyCode::Esc => app.discard_edit(),
+        _ => {}
+    }
+}
+
+fn handle_copy_pod_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.pod_list_state.selected().unwrap_or(0);
+    let last_idx = app.visible_indices().len().saturating_sub(1);
 
     match key_code {
         KeyCode::Up | KeyCode::Char('k') => {
-            app.context_list_state.select(Some(selected.saturating_sub(1)));
+            app.pod_list_state.select(Some(selected.saturating_sub(1)))
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.context_list_state.select(Some(if selected < last_idx {
+            app.pod_list_state.select(Some(if selected < last_idx {
                 selected + 1
             } else {
                 0
             }));
         }
         KeyCode::Enter => {
-            let ctx = app.contexts.get(selected).cloned();
-            if let Some(context_string) = ctx {
-                if let Err(e) = app.switch_context(&context_string) {
-                    app.message = format!("Error switching context: {}", e);
-                    app.state = AppState::Message;
-                } else {
-                    app.state = AppState::MainMenu;
-                }
+            if let Some(cloned_pod) = app.selected_pod_name() {
+                app.load_containers(&cloned_pod, ContainerAction::Copy);
+            }
+        }
+        KeyCode::Char('d') => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.describe("pod", &pod, true);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.view_manifest("pod", &pod, true);
+            }
+        }
+        KeyCode::Char('e') => app.open_pod_events(),
+        KeyCode::Char('x') | KeyCode::Delete => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.confirm_delete("pod", &pod, true);
             }
         }
+        KeyCode::Char('s') => app.sort_pods(app.pod_sort.next(), false),
+        KeyCode::Char('S') => app.sort_pods(app.pod_sort, !app.pod_sort_descending),
         KeyCode::Esc => {
-            app.state = AppState::MainMenu;
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::MainMenu;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_debug_pod_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.pod_list_state.selected().unwrap_or(0);
+    let last_idx = app.visible_indices().len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.pod_list_state.select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.pod_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.load_containers(&pod, ContainerAction::Debug);
+            }
+        }
+        KeyCode::Char('d') => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.describe("pod", &pod, true);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.view_manifest("pod", &pod, true);
+            }
+        }
+        KeyCode::Char('e') => app.open_pod_events(),
+        KeyCode::Char('x') | KeyCode::Delete => {
+            if let Some(pod) = app.selected_pod_name() {
+                app.confirm_delete("pod", &pod, true);
+            }
+        }
+        KeyCode::Char('s') => app.sort_pods(app.pod_sort.next(), false),
+        KeyCode::Char('S') => app.sort_pods(app.pod_sort, !app.pod_sort_descending),
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::MainMenu;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_debug_container_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.container_list_state.selected().unwrap_or(0);
+    let last_idx = app.containers.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.container_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.container_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => {
+            if let Some(container) = app.selected_container_name() {
+                app.selected_container = Some(container);
+                app.open_debug_image_selection();
+            }
+        }
+        KeyCode::Esc => app.state = AppState::DebugPodSelection,
+        _ => {}
+    }
+}
+
+fn handle_debug_image_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.debug_image_list_state.selected().unwrap_or(0);
+    let last_idx = app.debug_images.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.debug_image_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.debug_image_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => app.debug_pod(),
+        KeyCode::Esc => {
+            app.state = if app.containers.len() > 1 {
+                AppState::DebugContainerSelection
+            } else {
+                AppState::DebugPodSelection
+            };
+        }
+        _ => {}
+    }
+}
+
+fn handle_resource_kind_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.api_resource_list_state.selected().unwrap_or(0);
+    let last_idx = app.api_resources.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.api_resource_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.api_resource_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => {
+            if let Some(kind) = app.api_resources.get(selected).cloned() {
+                app.selected_kind = Some(kind);
+                app.load_resources();
+            }
+        }
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::MainMenu;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_resource_list(app: &mut App, key_code: KeyCode) {
+    let selected = app.resource_list_state.selected().unwrap_or(0);
+    let last_idx = app.visible_indices().len().saturating_sub(1);
+    let Some(kind) = app.selected_kind.clone() else {
+        app.state = AppState::ResourceKindSelection;
+        return;
+    };
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.resource_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.resource_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Char(' ') => {
+            app.toggle_mark();
+            app.resource_list_state.select(Some((selected + 1).min(last_idx)));
+        }
+        KeyCode::Char('a') => app.mark_all(),
+        KeyCode::Char('i') => app.invert_marks(),
+        KeyCode::Char('d') => {
+            let names = app.targets();
+            if !names.is_empty() {
+                app.describe_all(&kind.name, &names, kind.namespaced);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(name) = app.selected_resource_name() {
+                app.view_manifest(&kind.name, &name, kind.namespaced);
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Delete => {
+            let names = app.targets();
+            if !names.is_empty() {
+                app.confirm_delete_all(&kind.name, names, kind.namespaced);
+            }
+        }
+        KeyCode::Char('f') if kind.supports_port_forward() => {
+            if let Some(name) = app.selected_resource_name() {
+                let target = format!("{}/{}", kind.name, name);
+                app.open_port_forward(app.current_namespace(), &target);
+            }
+        }
+        KeyCode::Char('r') => app.load_resources(),
+        KeyCode::Esc if !app.marked.is_empty() => app.marked.clear(),
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::ResourceKindSelection;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_confirm_delete(app: &mut App, key_code: KeyCode) {
+    let Some(pending) = app.pending_delete.as_mut() else {
+        return;
+    };
+    // Without a name to type, y/n answer the dialog directly.
+    let typing = pending.confirm_text.is_some();
+    match key_code {
+        KeyCode::Enter => app.delete_confirmed(),
+        KeyCode::Char('y') if !typing => app.delete_confirmed(),
+        KeyCode::Char('n') if !typing => app.cancel_delete(),
+        KeyCode::Esc => app.cancel_delete(),
+        KeyCode::Up | KeyCode::BackTab => pending.focus_previous(),
+        KeyCode::Down | KeyCode::Tab => pending.focus_next(),
+        KeyCode::Left => pending.toggle(false),
+        KeyCode::Right => pending.toggle(true),
+        KeyCode::Char(c) => pending.push(c),
+        KeyCode::Backspace => pending.pop(),
+        _ => {}
+    }
+}
+
+fn handle_deployment_list(app: &mut App, key_code: KeyCode) {
+    let selected = app.deployment_list_state.selected().unwrap_or(0);
+    let last_idx = app.deployments.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.deployment_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.deployment_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Char(' ') => {
+            app.toggle_mark();
+            app.deployment_list_state.select(Some((selected + 1).min(last_idx)));
+        }
+        KeyCode::Char('a') => app.mark_all(),
+        KeyCode::Char('i') => app.invert_marks(),
+        KeyCode::Char('s') => app.open_scale_input(),
+        KeyCode::Char('r') => app.restart_selected(),
+        KeyCode::Char('u') => app.load_rollout_history(),
+        KeyCode::Char('w') => app.watch_rollout(),
+        KeyCode::Char('d') if !app.marked.is_empty() => {
+            let names = app.targets();
+            app.describe_all("deployment", &names, true);
+        }
+        KeyCode::Char('d') => {
+            if let Some(name) = app.selected_deployment().map(|d| d.name.clone()) {
+                app.describe("deployment", &name, true);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(name) = app.selected_deployment().map(|d| d.name.clone()) {
+                app.view_manifest("deployment", &name, true);
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Delete => {
+            let names = app.targets();
+            if !names.is_empty() {
+                app.confirm_delete_all("deployment", names, true);
+            }
+        }
+        KeyCode::Char('R') => app.load_deployments(),
+        KeyCode::Esc if !app.marked.is_empty() => app.marked.clear(),
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::MainMenu;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_scale_input(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Enter => app.scale_selected(),
+        KeyCode::Char(c) if c.is_ascii_digit() => app.scale_input.push(c),
+        KeyCode::Backspace => {
+            app.scale_input.pop();
+        }
+        KeyCode::Up => {
+            let replicas = app.scale_input.parse::<u32>().unwrap_or(0);
+            app.scale_input = replicas.saturating_add(1).to_string();
+        }
+        KeyCode::Down => {
+            let replicas = app.scale_input.parse::<u32>().unwrap_or(0);
+            app.scale_input = replicas.saturating_sub(1).to_string();
+        }
+        KeyCode::Esc => app.state = AppState::DeploymentList,
+        _ => {}
+    }
+}
+
+fn handle_revision_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.revision_list_state.selected().unwrap_or(0);
+    let last_idx = app.revisions.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.revision_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.revision_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => app.undo_to_selected_revision(),
+        KeyCode::Esc => app.state = AppState::DeploymentList,
+        _ => {}
+    }
+}
+
+fn handle_rollout_status(app: &mut App, key_code: KeyCode) {
+    if key_code == KeyCode::Esc {
+        app.close_rollout();
+    }
+}
+
+fn handle_workload_list(app: &mut App, kind: WorkloadKind, key_code: KeyCode) {
+    let selected = app.workload_list_state.selected().unwrap_or(0);
+    let last_idx = app.workloads.len().saturating_sub(1);
+    let name = app.selected_workload().map(|w| w.name.clone());
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.workload_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.workload_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Char(' ') => {
+            app.toggle_mark();
+            app.workload_list_state.select(Some((selected + 1).min(last_idx)));
+        }
+        KeyCode::Char('a') => app.mark_all(),
+        KeyCode::Char('i') => app.invert_marks(),
+        KeyCode::Char('r') if kind.restartable() => app.restart_workload(kind),
+        KeyCode::Char('p') if kind == WorkloadKind::CronJob => app.toggle_cronjob_suspend(),
+        KeyCode::Char('t') if kind == WorkloadKind::CronJob => app.trigger_cronjob(),
+        KeyCode::Char('c') if kind == WorkloadKind::Job => app.delete_finished_jobs(),
+        KeyCode::Char('d') if !app.marked.is_empty() => {
+            let names = app.targets();
+            app.describe_all(kind.resource(), &names, true);
+        }
+        KeyCode::Char('d') => {
+            if let Some(name) = name {
+                app.describe(kind.resource(), &name, true);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(name) = name {
+                app.view_manifest(kind.resource(), &name, true);
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Delete => {
+            let names = app.targets();
+            if !names.is_empty() {
+                app.confirm_delete_all(kind.resource(), names, true);
+            }
+        }
+        KeyCode::Char('R') => app.load_workloads(kind),
+        KeyCode::Esc if !app.marked.is_empty() => app.marked.clear(),
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::MainMenu;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_port_forward_input(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Enter => app.start_port_forward(),
+        KeyCode::Char(c) if c.is_ascii_digit() || c == ':' => app.port_forward_input.push(c),
+        KeyCode::Backspace => {
+            app.port_forward_input.pop();
+        }
+        KeyCode::Esc => app.cancel_port_forward(),
+        _ => {}
+    }
+}
+
+fn handle_port_forward_list(app: &mut App, key_code: KeyCode) {
+    let selected = app.port_forward_list_state.selected().unwrap_or(0);
+    let last_idx = app.port_forwards.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.port_forward_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.port_forward_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Char('x') | KeyCode::Delete => app.stop_selected_port_forward(),
+        KeyCode::Esc => app.state = AppState::MainMenu,
+        _ => {}
+    }
+}
+
+fn handle_event_list(app: &mut App, key_code: KeyCode) {
+    let selected = app.event_list_state.selected().unwrap_or(0);
+    let last_idx = app.visible_events().len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.event_list_state.select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.event_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Char('o') => app.toggle_event_object(),
+        KeyCode::Char('a') => app.toggle_events_all_namespaces(),
+        KeyCode::Char('r') => app.load_events(),
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.close_events();
+            }
         }
         _ => {}
     }
 }
+
+fn handle_node_list(app: &mut App, key_code: KeyCode) {
+    let selected = app.node_list_state.selected().unwrap_or(0);
+    let last_idx = app.nodes.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.node_list_state.select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.node_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => app.load_node_pods(),
+        KeyCode::Char('c') => app.cordon_selected(true),
+        KeyCode::Char('u') => app.cordon_selected(false),
+        KeyCode::Char('D') => app.open_drain_options(),
+        KeyCode::Char('d') => {
+            if let Some(node) = app.selected_node_name() {
+                app.describe("node", &node, false);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(node) = app.selected_node_name() {
+                app.view_manifest("node", &node, false);
+            }
+        }
+        KeyCode::Char('r') => app.load_nodes(),
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::MainMenu;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_drain_options(app: &mut App, key_code: KeyCode) {
+    let Some(pending) = app.pending_drain.as_mut() else {
+        return;
+    };
+    match key_code {
+        KeyCode::Enter => app.start_drain(),
+        KeyCode::Esc => app.cancel_drain_options(),
+        KeyCode::Up | KeyCode::BackTab => pending.focus_previous(),
+        KeyCode::Down | KeyCode::Tab => pending.focus_next(),
+        KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => pending.toggle(),
+        _ => {}
+    }
+}
+
+fn handle_drain_progress(app: &mut App, key_code: KeyCode) {
+    if key_code == KeyCode::Esc {
+        app.close_drain();
+    }
+}
+
+fn handle_secret_list(app: &mut App, key_code: KeyCode) {
+    let selected = app.secret_list_state.selected().unwrap_or(0);
+    let last_idx = app.secrets.len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.secret_list_state.select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.secret_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => app.open_secret(),
+        KeyCode::Char('d') => {
+            if let Some(name) = app.selected_secret().map(|s| s.name.clone()) {
+                app.describe("secret", &name, true);
+            }
+        }
+        KeyCode::Char('r') => app.load_secrets(),
+        KeyCode::Esc => {
+            let cancelled = app.worker.cancel_all();
+            if !cancelled {
+                app.state = AppState::MainMenu;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_secret_detail(app: &mut App, key_code: KeyCode) {
+    let Some(view) = app.secret_view.as_mut() else {
+        return;
+    };
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => view.select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => view.select_next(),
+        KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('v') => view.toggle_reveal(),
+        KeyCode::Char('c') => app.copy_secret_value(),
+        KeyCode::Esc => app.close_secret(),
+        _ => {}
+    }
+}
+
+fn handle_namespace_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.namespace_list_state.selected().unwrap_or(0);
+    let last_idx = app.visible_indices().len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.namespace_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.namespace_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => {
+            if let Some(ns) = app.highlighted_name() {
+                app.selected_namespace = Some(ns);
+                app.state = AppState::MainMenu;
+            }
+        }
+        KeyCode::Char('d') => {
+            if let Some(ns) = app.highlighted_name() {
+                app.describe("namespace", &ns, false);
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(ns) = app.highlighted_name() {
+                app.view_manifest("namespace", &ns, false);
+            }
+        }
+        KeyCode::Esc => app.state = AppState::MainMenu,
+        _ => {}
+    }
+}
+
+fn handle_context_selection(app: &mut App, key_code: KeyCode) {
+    let selected = app.context_list_state.selected().unwrap_or(0);
+    let last_idx = app.visible_indices().len().saturating_sub(1);
+
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.context_list_state
+                .select(Some(selected.saturating_sub(1)))
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.context_list_state.select(Some(if selected < last_idx {
+                selected + 1
+            } else {
+                0
+            }));
+        }
+        KeyCode::Enter => {
+            if let Some(context_string) = app.highlighted_name() {
+                if let Err(e) = app.switch_context(&context_string) {
+                    app.message = format!("Error switching context: {}", e);
+                    app.state = AppState::Message;
+                } else {
+                    app.state = AppState::MainMenu;
+                }
+            }
+        }
+        KeyCode::Esc => app.state = AppState::MainMenu,
+        _ => {}
+    }
+}
+
+fn handle_copy_pod_form(app: &mut App, key_code: KeyCode) {
+    let form = &mut app.copy_form;
+    match key_code {
+        KeyCode::Enter => {
+            if form.new_name.trim().is_empty() {
+                app.status_message = Some("Please enter a new pod name".to_string());
+            } else {
+                app.copy_pod();
+            }
+        }
+        KeyCode::Up | KeyCode::BackTab => form.focus_previous(),
+        KeyCode::Down | KeyCode::Tab => form.focus_next(),
+        KeyCode::Left => form.toggle(false),
+        KeyCode::Right => form.toggle(true),
+        KeyCode::Char(c) => form.push(c),
+        KeyCode::Backspace => form.pop(),
+        KeyCode::Esc => {
+            app.state = AppState::PodSelection;
+        }
+        _ => {}
+    }
+}
+
+pub fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let vertical_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(3),
+        ])
+        .split(f.size());
+
+    let header = Paragraph::new(format!(
+        " Context: {} | Namespace: {} ",
+        app.selected_context.as_deref().unwrap_or("None"),
+        app.current_namespace()
+    ))
+    .block(Block::default().borders(Borders::BOTTOM));
+    f.render_widget(header, vertical_chunks[0]);
+
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(vertical_chunks[1]);
+
+    let command_items: Vec<ListItem> = app
+        .commands
+        .iter()
+        .map(|c| ListItem::new(c.as_str()))
+        .collect();
+    let commands = List::new(command_items)
+        .block(Block::default().borders(Borders::ALL).title("Commands"))
+        .highlight_symbol("▶");
+    f.render_stateful_widget(commands, main_chunks[0], &mut app.list_state);
+
+    // Dialogs are drawn over the screen they were opened from.
+    let screen = match (&app.pending_delete, app.state) {
+        (Some(pending), AppState::ConfirmDelete) => pending.return_to,
+        (_, AppState::DrainOptions) => AppState::NodeList,
+        (_, state) => state,
+    };
+    match screen {
+        AppState::MainMenu => {
+            render_output_preview(f, app, main_chunks[1]);
+        }
+        AppState::NamespaceSelection => {
+            let items = filtered_items(app);
+            let title = app.filter_title("Select Namespace");
+            render_item_panel(f, main_chunks[1], items, &mut app.namespace_list_state, &title)
+        }
+        AppState::ContextSelection => {
+            let items = filtered_items(app);
+            let title = app.filter_title("Select Context");
+            render_item_panel(f, main_chunks[1], items, &mut app.context_list_state, &title)
+        }
+        AppState::ExecPodSelection => {
+            render_pod_table(f, app, main_chunks[1], "Select Pod to Exec")
+        }
+        AppState::PodSelection => render_pod_table(f, app, main_chunks[1], "Select Pod to Copy"),
+        AppState::DebugPodSelection => {
+            render_pod_table(f, app, main_chunks[1], "Select Pod to Debug")
+        }
+        AppState::CopyPodForm => render_copy_pod_form(f, app, main_chunks[1]),
+        AppState::LogContainerSelection
+        | AppState::ExecContainerSelection
+        | AppState::DebugContainerSelection => {
+            let containers: Vec<String> =
+                app.containers.iter().map(|c| c.display_name()).collect();
+            render_list_panel(
+                f,
+                main_chunks[1],
+                &containers,
+                &mut app.container_list_state,
+                "Select Container",
+            )
+        }
+        AppState::ShellSelection => {
+            let shells: Vec<String> = Shell::ALL.iter().map(|s| s.label().to_string()).collect();
+            render_list_panel(
+                f,
+                main_chunks[1],
+                &shells,
+                &mut app.shell_list_state,
+                "Select Shell",
+            )
+        }
+        AppState::DebugImageSelection => render_list_panel(
+            f,
+            main_chunks[1],
+            &app.debug_images,
+            &mut app.debug_image_list_state,
+            "Select Debug Image",
+        ),
+        AppState::ExecCommandInput => render_text_input(
+            f,
+            main_chunks[1],
+            &format!(
+                "Exec into pod: {}\nEnter command to run:",
+                app.selected_pod.as_deref().unwrap_or("None")
+            ),
+            "Command",
+            &app.exec_command,
+        ),
+        AppState::PodLogs => render_logs_panel(f, app, main_chunks[1]),
+        AppState::Describe => render_describe_panel(f, app, main_chunks[1]),
+        AppState::Manifest => render_manifest_panel(f, app, main_chunks[1]),
+        AppState::EditConfirm => render_edit_confirm_panel(f, app, main_chunks[1]),
+        AppState::Applying => {
+            let title = busy_title(app, "Apply");
+            let panel = Paragraph::new("Waiting for kubectl apply to finish")
+                .block(Block::default().borders(Borders::ALL).title(title));
+            f.render_widget(panel, main_chunks[1]);
+        }
+        AppState::ResourceKindSelection => {
+            let kinds: Vec<String> = app.api_resources.iter().map(|r| r.display_name()).collect();
+            render_list_panel(
+                f,
+                main_chunks[1],
+                &kinds,
+                &mut app.api_resource_list_state,
+                "Select Resource Kind",
+            )
+        }
+        AppState::ResourceList => {
+            let title = match &app.selected_kind {
+                Some(kind) if kind.namespaced => {
+                    format!("{} in {}", kind.name, app.current_namespace())
+                }
+                Some(kind) => kind.name.clone(),
+                None => "Resources".to_string(),
+            };
+            let items = filtered_items(app);
+            let title = app.filter_title(&marked_title(&title, &app.marked));
+            render_item_panel(f, main_chunks[1], items, &mut app.resource_list_state, &title)
+        }
+        AppState::ConfirmDelete => {}
+        AppState::DeploymentList => render_deployment_table(f, app, main_chunks[1]),
+        AppState::ScaleInput => {
+            let name = app.selected_deployment().map(|d| d.name.clone()).unwrap_or_default();
+            render_text_input(
+                f,
+                main_chunks[1],
+                &format!("Scale deployment: {}\nEnter the number of replicas:", name),
+                "Replicas",
+                &app.scale_input,
+            )
+        }
+        AppState::RevisionSelection => {
+            let revisions: Vec<String> = app.revisions.iter().map(|r| r.display()).collect();
+            render_list_panel(
+                f,
+                main_chunks[1],
+                &revisions,
+                &mut app.revision_list_state,
+                "Roll Back to Revision",
+            )
+        }
+        AppState::RolloutStatus => render_rollout_panel(f, app, main_chunks[1]),
+        AppState::StatefulSetList => {
+            render_workload_table(f, app, main_chunks[1], WorkloadKind::StatefulSet)
+        }
+        AppState::DaemonSetList => {
+            render_workload_table(f, app, main_chunks[1], WorkloadKind::DaemonSet)
+        }
+        AppState::JobList => render_workload_table(f, app, main_chunks[1], WorkloadKind::Job),
+        AppState::PortForwardInput => {
+            let (target, namespace) = app
+                .pending_forward
+                .as_ref()
+                .map(|p| (p.target.clone(), p.namespace.clone()))
+                .unwrap_or_default();
+            render_text_input(
+                f,
+                main_chunks[1],
+                &format!(
+                    "Forward a local port to {} in {}\nEnter local:remote, or one port for both:",
+                    target, namespace
+                ),
+                "Ports",
+                &app.port_forward_input,
+            )
+        }
+        AppState::PortForwardList => render_port_forward_panel(f, app, main_chunks[1]),
+        AppState::BulkSummary => render_bulk_summary_panel(f, app, main_chunks[1]),
+        AppState::EventList => render_event_table(f, app, main_chunks[1]),
+        AppState::NodeList => render_node_table(f, app, main_chunks[1]),
+        AppState::NodePodList => {
+            let title = format!("Pods on {}", app.selected_node.clone().unwrap_or_default());
+            render_pod_table(f, app, main_chunks[1], &title)
+        }
+        AppState::DrainOptions => {}
+        AppState::DrainProgress => render_drain_panel(f, app, main_chunks[1]),
+        AppState::SecretList => render_secret_table(f, app, main_chunks[1]),
+        AppState::SecretDetail => render_secret_panel(f, app, main_chunks[1]),
+        AppState::CronJobList => {
+            render_workload_table(f, app, main_chunks[1], WorkloadKind::CronJob)
+        }
+        AppState::ShowOutput => render_output_panel(f, app, main_chunks[1]),
+        AppState::Message => render_message_panel(f, app, main_chunks[1]),
+    }
+
+    let status = match app.state {
+        _ if app.is_filtering() && app.is_pod_list() => {
+            "Type to filter, or app=api,tier!=db to select by label  [↑/↓] Navigate  [Enter] Done  [Esc] Clear"
+        }
+        _ if app.is_filtering() => "Type to filter  [↑/↓] Navigate  [Enter] Done  [Esc] Clear",
+        AppState::ContextSelection => "[↑/↓ or j/k] Navigate  [Enter/Right] Select  [/] Filter  [Esc] Back  [q] Quit",
+        AppState::LogContainerSelection
+        | AppState::ExecContainerSelection
+        | AppState::ShellSelection
+        | AppState::DebugContainerSelection
+        | AppState::DebugImageSelection => "[↑/↓ or j/k] Navigate  [Enter/Right] Select  [Esc] Back  [q] Quit",
+        AppState::PodSelection | AppState::DebugPodSelection => "[↑/↓ or j/k] Navigate  [Enter] Select  [/] Filter  [d] Describe  [y] YAML  [e] Events  [x] Delete  [s/S] Sort  [Esc] Back  [q] Quit",
+        AppState::ExecPodSelection | AppState::NodePodList => "[↑/↓ or j/k] Navigate  [Enter] Exec  [/] Filter  [Space] Mark  [a/i] All/Invert  [l] Logs  [f] Forward  [d] Describe  [y] YAML  [e] Events  [x] Delete  [r] Restart owner  [s/S] Sort  [Esc] Back  [q] Quit",
+        AppState::NamespaceSelection => "[↑/↓ or j/k] Navigate  [Enter] Select  [/] Filter  [d] Describe  [y] YAML  [Esc] Back  [q] Quit",
+        AppState::Manifest if app.manifest.as_ref().is_some_and(|m| m.searching) => {
+            "Type to search  [Enter] Find  [Esc] Cancel"
+        }
+        AppState::Manifest if app.manifest.as_ref().is_some_and(|m| m.hide_noise) => {
+            "[↑/↓ PgUp/PgDn ←/→] Scroll  [/] Search  [n/N] Next/Prev  [h] Show managedFields/status  [e] Edit  [Esc] Back  [q] Quit"
+        }
+        AppState::Manifest => "[↑/↓ PgUp/PgDn ←/→] Scroll  [/] Search  [n/N] Next/Prev  [h] Hide managedFields/status  [e] Edit  [Esc] Back  [q] Quit",
+        AppState::ResourceKindSelection => "[↑/↓ or j/k] Navigate  [Enter] Select  [Esc] Back  [q] Quit",
+        AppState::ResourceList if app.selected_kind.as_ref().is_some_and(|k| k.supports_port_forward()) => {
+            "[↑/↓ or j/k] Navigate  [/] Filter  [Space] Mark  [a/i] All/Invert  [f] Forward  [d] Describe  [y] YAML  [x] Delete  [r] Refresh  [Esc] Back  [q] Quit"
+        }
+        AppState::ResourceList => "[↑/↓ or j/k] Navigate  [/] Filter  [Space] Mark  [a/i] All/Invert  [d] Describe  [y] YAML  [x] Delete  [r] Refresh  [Esc] Back  [q] Quit",
+        AppState::ConfirmDelete if app.is_text_input() => {
+            "Type to confirm  [↑/↓/Tab] Option  [←/→/Space] Change  [Enter] Delete  [Esc] Cancel"
+        }
+        AppState::ConfirmDelete => "[y/Enter] Delete  [↑/↓/Tab] Option  [←/→/Space] Change  [n/Esc] Cancel  [q] Quit",
+        AppState::DeploymentList => "[↑/↓ or j/k] Navigate  [Space] Mark  [a/i] All/Invert  [s] Scale  [r] Restart  [u] Undo  [w] Watch rollout  [d] Describe  [y] YAML  [x] Delete  [R] Refresh  [Esc] Back  [q] Quit",
+        AppState::ScaleInput => "[0-9 ↑/↓] Replicas  [Enter] Scale  [Esc] Back",
+        AppState::RevisionSelection => "[↑/↓ or j/k] Navigate  [Enter] Roll back  [Esc] Back  [q] Quit",
+        AppState::RolloutStatus => "[Esc] Back  [q] Quit",
+        AppState::PortForwardInput => "[0-9 :] Ports  [Enter] Forward  [Esc] Back",
+        AppState::PortForwardList => "[↑/↓ or j/k] Navigate  [x] Stop  [Esc] Back  [q] Quit",
+        AppState::BulkSummary => "Press any key to return, or [q] Quit",
+        AppState::EventList if app.event_object.is_some() => "[↑/↓ or j/k] Navigate  [o] All objects  [a] All namespaces  [r] Refresh  [Esc] Back  [q] Quit",
+        AppState::EventList => "[↑/↓ or j/k] Navigate  [o] Only this object  [a] All namespaces  [r] Refresh  [Esc] Back  [q] Quit",
+        AppState::NodeList => "[↑/↓ or j/k] Navigate  [Enter] Pods  [c] Cordon  [u] Uncordon  [D] Drain  [d] Describe  [y] YAML  [r] Refresh  [Esc] Back  [q] Quit",
+        AppState::DrainOptions => "[Enter] Drain  [↑/↓/Tab] Option  [←/→/Space] Toggle  [Esc] Cancel  [q] Quit",
+        AppState::DrainProgress if app.drain.as_ref().is_some_and(|d| d.is_running()) => "[Esc] Stop drain  [q] Quit",
+        AppState::DrainProgress => "[Esc] Back  [q] Quit",
+        AppState::SecretList => "[↑/↓ or j/k] Navigate  [Enter] Keys  [d] Describe  [r] Refresh  [Esc] Back  [q] Quit",
+        AppState::SecretDetail => "[↑/↓ or j/k] Navigate  [Enter/v] Reveal/Hide  [c] Copy value  [Esc] Back  [q] Quit",
+        AppState::StatefulSetList | AppState::DaemonSetList => "[↑/↓ or j/k] Navigate  [Space] Mark  [a/i] All/Invert  [r] Restart  [d] Describe  [y] YAML  [x] Delete  [R] Refresh  [Esc] Back  [q] Quit",
+        AppState::JobList => "[↑/↓ or j/k] Navigate  [Space] Mark  [a/i] All/Invert  [c] Delete finished  [d] Describe  [y] YAML  [x] Delete  [R] Refresh  [Esc] Back  [q] Quit",
+        AppState::CronJobList => "[↑/↓ or j/k] Navigate  [Space] Mark  [a/i] All/Invert  [t] Trigger  [p] Suspend/Resume  [d] Describe  [y] YAML  [x] Delete  [R] Refresh  [Esc] Back  [q] Quit",
+        AppState::EditConfirm => "[↑/↓ PgUp/PgDn] Scroll diff  [y] Apply  [n/Esc] Discard  [q] Quit",
+        AppState::Applying => "Waiting for kubectl apply  [q] Quit",
+        AppState::Describe => "[↑/↓ PgUp/PgDn Home/End] Scroll  [Enter/Space] Fold  [z] Fold all  [Esc] Back  [q] Quit",
+        AppState::PodLogs => "[↑/↓ PgUp/PgDn Home/End] Scroll  [f] Follow  [p] Previous  [t] Timestamps  [Esc] Back  [q] Quit",
+        AppState::CopyPodForm => "[↑/↓/Tab] Field  [←/→/Space] Change  [Enter] Copy  [Esc] Back",
+        AppState::ExecCommandInput => "[Enter] Submit  [Esc] Back",
+        AppState::Message | AppState::ShowOutput => "Press any key to return to main menu, or [q] Quit",
+        AppState::MainMenu if app.worker.is_busy() => {
+            "[↑/↓ or j/k] Navigate  [Enter/Right] Select  [Esc] Cancel  [q] Quit"
+        }
+        AppState::MainMenu => "[↑/↓ or j/k] Navigate  [Enter/Right] Select  [q] Quit",
+    };
+    if app.state == AppState::ConfirmDelete {
+        render_delete_dialog(f, app, vertical_chunks[1]);
+    }
+    if app.state == AppState::DrainOptions {
+        render_drain_dialog(f, app, vertical_chunks[1]);
+    }
+
+    let mut status_lines = vec![Spans::from(status)];
+    if let Some(message) = &app.status_message {
+        status_lines.push(Spans::from(message.as_str()));
+    }
+    let status_bar = Paragraph::new(status_lines).block(Block::default().borders(Borders::TOP));
+    f.render_widget(status_bar, vertical_chunks[2]);
+}
+
+fn render_output_preview<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let selected = app.list_state.selected().unwrap_or(0);
+    if app.worker.is_busy() {
+        render_loading_panel(f, app, area);
+    } else if selected == 2 && !app.output.is_empty() {
+        let output = Paragraph::new(app.output.as_str())
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title("Pods Preview"));
+        f.render_widget(output, area);
+    } else if selected == 12 && !app.output.is_empty() {
+        let output = Paragraph::new(app.output.as_str())
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title("Recent Warnings"));
+        f.render_widget(output, area);
+    } else {
+        render_default_panel(f, area);
+    }
+}
+
+fn render_loading_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let label = app.worker.current_label().unwrap_or("Loading");
+    let loading = Paragraph::new(format!(
+        "{} {}...\n\n[Esc] Cancel",
+        worker::spinner_frame(app.tick),
+        label
+    ))
+    .block(Block::default().borders(Borders::ALL).title("Loading"));
+    f.render_widget(loading, area);
+}
+
+fn render_list_panel<B: Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    items: &[String],
+    state: &mut ListState,
+    title: &str,
+) {
+    let list_items: Vec<ListItem> = items.iter().map(|i| ListItem::new(i.as_str())).collect();
+    let list = List::new(list_items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_symbol("▶");
+    f.render_stateful_widget(list, area, state);
+}
+
+/// Like `render_list_panel`, for items that carry their own styling.
+fn render_item_panel<B: Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    items: Vec<ListItem<'static>>,
+    state: &mut ListState,
+    title: &str,
+) {
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_symbol("▶");
+    f.render_stateful_widget(list, area, state);
+}
+
+fn render_pod_table<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect, title: &str) {
+    // Every pod on a node shares it, so show where they live instead.
+    let on_node = app.list_screen() == AppState::NodePodList;
+    let columns = [
+        ("NAME", Some(PodSortColumn::Name)),
+        ("READY", None),
+        ("STATUS", Some(PodSortColumn::Status)),
+        ("RESTARTS", Some(PodSortColumn::Restarts)),
+        ("AGE", Some(PodSortColumn::Age)),
+        ("CPU", None),
+        ("MEMORY", None),
+        ("IP", None),
+        if on_node {
+            ("NAMESPACE", None)
+        } else {
+            ("NODE", Some(PodSortColumn::Node))
+        },
+    ];
+    let header_cells = columns.iter().map(|(label, column)| {
+        if *column == Some(app.pod_sort) {
+            let arrow = if app.pod_sort_descending { "▼" } else { "▲" };
+            Cell::from(format!("{}{}", label, arrow))
+        } else {
+            Cell::from(*label)
+        }
+    });
+    let header = Row::new(header_cells).style(Style::default().add_modifier(Modifier::BOLD));
+
+    let names = filtered_names(app);
+    let rows: Vec<Row> = app
+        .visible_indices()
+        .into_iter()
+        .map(|i| &app.pods[i])
+        .zip(names)
+        .map(|(pod, name)| {
+            let style = if pod.status == "Terminating" {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                change_style(app.row_change(&pod.name))
+            };
+            // Without metrics the usage columns stay empty.
+            let (cpu, memory) = match app.pod_usage(pod) {
+                Some(usage) => (
+                    format!("{:<6}{}", usage.cpu_display(), app.pod_sparkline(pod)),
+                    usage.memory_display(),
+                ),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(pod.ready_display()),
+                Cell::from(pod.status.clone()).style(Style::default().fg(pod_status_color(pod))),
+                Cell::from(pod.restarts.to_string()),
+                Cell::from(pod.age_display()),
+                Cell::from(cpu),
+                Cell::from(memory),
+                Cell::from(pod.ip.clone().unwrap_or_else(|| "<none>".to_string())),
+                Cell::from(if on_node {
+                    pod.namespace.clone()
+                } else {
+                    pod.node.clone().unwrap_or_else(|| "<none>".to_string())
+                }),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Percentage(30),
+        Constraint::Length(6),
+        Constraint::Length(18),
+        Constraint::Length(9),
+        Constraint::Length(6),
+        Constraint::Length(6 + SPARKLINE_WIDTH as u16),
+        Constraint::Length(7),
+        Constraint::Length(16),
+        Constraint::Percentage(20),
+    ];
+    let title = busy_title(app, &app.filter_title(&marked_title(title, &app.marked)));
+    let table = Table::new(rows)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(&widths)
+        .highlight_symbol("▶");
+    f.render_stateful_widget(table, area, &mut app.pod_list_state);
+}
+
+/// The names shown in the list on screen, with the characters matching the
+/// filter highlighted. Once anything is marked, marked names are prefixed
+/// with a bullet and the rest with padding.
+fn filtered_names(app: &App) -> Vec<Spans<'static>> {
+    let names = app.list_names();
+    app.visible_indices()
+        .into_iter()
+        .map(|i| {
+            let name = &names[i];
+            let mut spans = match (app.marked.is_empty(), app.marked.contains(name)) {
+                (true, _) => Vec::new(),
+                (false, true) => vec![Span::raw("● ")],
+                (false, false) => vec![Span::raw("  ")],
+            };
+            spans.extend(filter::highlight(name, app.fuzzy_query()));
+            Spans::from(spans)
+        })
+        .collect()
+}
+
+/// `filtered_names` as list items, coloured by how the latest watch
+/// refresh changed them.
+fn filtered_items(app: &App) -> Vec<ListItem<'static>> {
+    filtered_names(app)
+        .into_iter()
+        .zip(app.visible_names())
+        .map(|(spans, name)| ListItem::new(spans).style(change_style(app.row_change(&name))))
+        .collect()
+}
+
+fn change_style(change: Option<RowChange>) -> Style {
+    match change {
+        Some(RowChange::Added) => Style::default().fg(Color::Green),
+        Some(RowChange::Changed) => Style::default().fg(Color::Yellow),
+        None => Style::default(),
+    }
+}
+
+fn marked_title(title: &str, marked: &BTreeSet<String>) -> String {
+    if marked.is_empty() {
+        title.to_string()
+    } else {
+        format!("{} ({} marked)", title, marked.len())
+    }
+}
+
+/// Each item of a bulk action with its output or error.
+fn render_bulk_summary_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let Some(summary) = &app.bulk else {
+        return;
+    };
+    let lines: Vec<Spans> = summary
+        .results
+        .iter()
+        .map(|(item, result)| match result {
+            Ok(output) => Spans::from(vec![
+                Span::styled(format!("✓ {}", item), Style::default().fg(Color::Green)),
+                Span::raw(format!("  {}", output.lines().last().unwrap_or_default())),
+            ]),
+            Err(e) => Spans::from(vec![
+                Span::styled(format!("✗ {}", item), Style::default().fg(Color::Red)),
+                Span::raw(format!("  {}", e.lines().last().unwrap_or_default())),
+            ]),
+        })
+        .collect();
+    let color = if summary.failed() > 0 { Color::Red } else { Color::Green };
+    let panel = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .title(summary.title()),
+    );
+    f.render_widget(panel, area);
+}
+
+/// The panel title with the spinner and label of any running job appended.
+fn busy_title(app: &App, title: &str) -> String {
+    match app.worker.current_label() {
+        Some(label) => format!("{} {} {}...", title, worker::spinner_frame(app.tick), label),
+        None => title.to_string(),
+    }
+}
+
+/// A table with a bold header row, as used by the workload views.
+fn render_resource_table<B: Backend>(
+    f: &mut Frame<B>,
+    area: Rect,
+    title: String,
+    headers: &[&str],
+    rows: Vec<Row>,
+    widths: &[Constraint],
+    state: &mut TableState,
+) {
+    let header = Row::new(headers.iter().map(|h| Cell::from(*h)))
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new(rows)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .widths(widths)
+        .highlight_symbol("▶");
+    f.render_stateful_widget(table, area, state);
+}
+
+fn render_deployment_table<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let rows: Vec<Row> = app
+        .deployments
+        .iter()
+        .zip(filtered_names(app))
+        .map(|(d, name)| {
+            let color = if d.is_available() { Color::Green } else { Color::Yellow };
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(d.ready_display()).style(Style::default().fg(color)),
+                Cell::from(d.updated.to_string()),
+                Cell::from(d.available.to_string()),
+                Cell::from(d.age_display()),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Percentage(40),
+        Constraint::Length(8),
+        Constraint::Length(11),
+        Constraint::Length(10),
+        Constraint::Length(6),
+    ];
+    let title = format!("Deployments in {}", app.current_namespace());
+    let title = busy_title(app, &marked_title(&title, &app.marked));
+    render_resource_table(
+        f,
+        area,
+        title,
+        &["NAME", "READY", "UP-TO-DATE", "AVAILABLE", "AGE"],
+        rows,
+        &widths,
+        &mut app.deployment_list_state,
+    );
+}
+
+fn render_node_table<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let rows: Vec<Row> = app
+        .nodes
+        .iter()
+        .map(|n| {
+            let color = match (n.ready, n.unschedulable) {
+                (false, _) => Color::Red,
+                (true, true) => Color::Yellow,
+                (true, false) => Color::Green,
+            };
+            let (cpu_used, memory_used) = match app.metrics.nodes.latest(&n.name) {
+                Some(usage) => (n.cpu_usage_display(&usage), n.memory_usage_display(&usage)),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            Row::new(vec![
+                Cell::from(n.name.clone()),
+                Cell::from(n.status_display()).style(Style::default().fg(color)),
+                Cell::from(n.roles_display()),
+                Cell::from(n.version.clone()),
+                Cell::from(n.cpu.clone()),
+                Cell::from(n.memory_display()),
+                Cell::from(cpu_used),
+                Cell::from(memory_used),
+                Cell::from(n.pods.to_string()),
+                Cell::from(n.taints_display()),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Percentage(25),
+        Constraint::Length(26),
+        Constraint::Length(14),
+        Constraint::Length(10),
+        Constraint::Length(5),
+        Constraint::Length(8),
+        Constraint::Length(11),
+        Constraint::Length(12),
+        Constraint::Length(5),
+        Constraint::Percentage(30),
+    ];
+    let title = busy_title(app, "Nodes");
+    render_resource_table(
+        f,
+        area,
+        title,
+        &[
+            "NAME", "STATUS", "ROLES", "VERSION", "CPU", "MEMORY", "CPU USED", "MEM USED", "PODS",
+            "TAINTS",
+        ],
+        rows,
+        &widths,
+        &mut app.node_list_state,
+    );
+}
+
+fn render_drain_dialog<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let Some(pending) = &app.pending_drain else {
+        return;
+    };
+    let dialog = ConfirmDialog {
+        title: format!("Drain {}", pending.node),
+        color: Color::Yellow,
+        body: vec![
+            Spans::from(format!("Evict every pod from {} and cordon it?", pending.node)),
+            Spans::from(""),
+        ],
+        fields: DrainField::ALL
+            .into_iter()
+            .map(|field| {
+                let value = if pending.is_set(field) { "[x]" } else { "[ ]" };
+                (field.label().to_string(), value.to_string())
+            })
+            .collect(),
+        focus: pending.focus,
+    };
+    render_confirm_dialog(f, dialog, area);
+}
+
+fn render_drain_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let Some(drain) = &app.drain else {
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(area);
+
+    let evicting = drain.evicting.len();
+    let ratio = match (drain.status, evicting) {
+        (DrainStatus::Succeeded, _) => 1.0,
+        (_, 0) => 0.0,
+        _ => (drain.evicted.len() as f64 / evicting as f64).min(1.0),
+    };
+    let color = match drain.status {
+        DrainStatus::Running => Color::Yellow,
+        DrainStatus::Succeeded => Color::Green,
+        DrainStatus::Failed | DrainStatus::Stopped => Color::Red,
+    };
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} {}", drain.status.label(), drain.node)),
+        )
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio)
+        .label(format!("{} of {} pods evicted", drain.evicted.len(), evicting));
+    f.render_widget(gauge, chunks[0]);
+
+    let visible = chunks[1].height.saturating_sub(2) as usize;
+    let mut lines: Vec<Spans> = drain
+        .lines
+        .iter()
+        .skip(drain.lines.len().saturating_sub(visible))
+        .map(|line| Spans::from(line.as_str()))
+        .collect();
+    if drain.is_running() && lines.len() < visible {
+        lines.push(Spans::from(Span::styled(
+            format!("{} Waiting for pods to be evicted", worker::spinner_frame(app.tick)),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let output = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Output"));
+    f.render_widget(output, chunks[1]);
+}
+
+fn render_secret_table<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let rows: Vec<Row> = app
+        .secrets
+        .iter()
+        .map(|s| {
+            Row::new(vec![
+                Cell::from(s.name.clone()),
+                Cell::from(s.secret_type.clone()),
+                Cell::from(s.data.len().to_string()),
+                Cell::from(s.age_display()),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Percentage(45),
+        Constraint::Percentage(40),
+        Constraint::Length(5),
+        Constraint::Length(6),
+    ];
+    let title = busy_title(app, &format!("Secrets in {}", app.current_namespace()));
+    render_resource_table(
+        f,
+        area,
+        title,
+        &["NAME", "TYPE", "DATA", "AGE"],
+        rows,
+        &widths,
+        &mut app.secret_list_state,
+    );
+}
+
+/// Keys of a secret with their values masked unlike the plain output panel,
+/// plus the certificate of TLS secrets.
+fn render_secret_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let Some(view) = &app.secret_view else {
+        return;
+    };
+    let mut area = area;
+    if let Some(cert) = &view.certificate {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(3)])
+            .split(area);
+        let color = if cert.is_expired() {
+            Color::Red
+        } else if cert.expires_soon() {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let lines = vec![
+            Spans::from(format!("Subject  {}", cert.subject)),
+            Spans::from(format!("Issuer   {}", cert.issuer)),
+            Spans::from(vec![
+                Span::raw("Expires  "),
+                Span::styled(cert.expiry_display(), Style::default().fg(color)),
+            ]),
+        ];
+        let panel = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Certificate"));
+        f.render_widget(panel, chunks[0]);
+        area = chunks[1];
+    }
+
+    let width = view.secret.data.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    for (index, key) in view.secret.data.keys().enumerate() {
+        let selected = index == view.selected;
+        if selected {
+            selected_line = lines.len();
+        }
+        let key_style = if selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        };
+        let value_style = if view.revealed.contains(key) {
+            Style::default()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let value = view.display_value(key);
+        for (i, line) in value.lines().chain(value.is_empty().then_some("")).enumerate() {
+            let label = if i == 0 { key.as_str() } else { "" };
+            lines.push(Spans::from(vec![
+                Span::raw(if selected && i == 0 { "▶" } else { " " }),
+                Span::styled(
+                    format!("{:<width$}", label, width = width),
+                    if i == 0 { key_style } else { Style::default() },
+                ),
+                Span::raw("  "),
+                Span::styled(line.to_string(), value_style),
+            ]));
+        }
+    }
+
+    // Keep the selected key on screen when revealed values run long.
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = (selected_line + 1).saturating_sub(visible);
+    let panel = Paragraph::new(lines).scroll((scroll as u16, 0)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} ({})", view.secret.name, view.secret.secret_type)),
+    );
+    f.render_widget(panel, area);
+}
+
+fn render_event_table<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let all_namespaces = app.events_all_namespaces;
+    let rows: Vec<Row> = app
+        .visible_events()
+        .into_iter()
+        .map(|e| {
+            let mut cells = Vec::new();
+            if all_namespaces {
+                cells.push(Cell::from(e.namespace.clone()));
+            }
+            cells.extend([
+                Cell::from(e.last_seen_display()),
+                Cell::from(e.event_type.clone()),
+                Cell::from(e.reason.clone()),
+                Cell::from(e.object_display()),
+                Cell::from(e.message.clone()),
+            ]);
+            let row = Row::new(cells);
+            if e.is_warning() {
+                row.style(Style::default().fg(Color::Yellow))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let mut headers = vec!["LAST SEEN", "TYPE", "REASON", "OBJECT", "MESSAGE"];
+    let mut widths = vec![
+        Constraint::Length(12),
+        Constraint::Length(8),
+        Constraint::Length(18),
+        Constraint::Percentage(25),
+        Constraint::Percentage(50),
+    ];
+    if all_namespaces {
+        headers.insert(0, "NAMESPACE");
+        widths.insert(0, Constraint::Length(14));
+    }
+    let mut title = if all_namespaces {
+        "Events in all namespaces".to_string()
+    } else {
+        format!("Events in {}", app.current_namespace())
+    };
+    if let Some(object) = &app.event_object {
+        title.push_str(&format!(" for {}", object.display()));
+    }
+    let title = busy_title(app, &title);
+    render_resource_table(
+        f,
+        area,
+        title,
+        &headers,
+        rows,
+        &widths,
+        &mut app.event_list_state,
+    );
+}
+
+/// Active forwards above the details of the selected one, including the
+/// stderr of a forward that failed.
+fn render_port_forward_panel<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(6)])
+        .split(area);
+
+    let rows: Vec<Row> = app
+        .port_forwards
+        .iter()
+        .map(|forward| {
+            let color = match forward.status {
+                ForwardStatus::Starting => Color::Yellow,
+                ForwardStatus::Active => Color::Green,
+                ForwardStatus::Failed => Color::Red,
+                ForwardStatus::Exited => Color::DarkGray,
+            };
+            Row::new(vec![
+                Cell::from(forward.target.clone()),
+                Cell::from(forward.namespace.clone()),
+                Cell::from(forward.ports_display()),
+                Cell::from(forward.status.label()).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Percentage(40),
+        Constraint::Percentage(25),
+        Constraint::Length(12),
+        Constraint::Length(9),
+    ];
+    render_resource_table(
+        f,
+        chunks[0],
+        "Port Forwards".to_string(),
+        &["TARGET", "NAMESPACE", "PORTS", "STATUS"],
+        rows,
+        &widths,
+        &mut app.port_forward_list_state,
+    );
+
+    let selected = app.port_forward_list_state.selected().unwrap_or(0);
+    let details = match app.port_forwards.get(selected) {
+        None => Paragraph::new("No port forwards. Press [f] on a pod or service to start one."),
+        Some(forward) if !forward.errors.is_empty() => {
+            let lines: Vec<Spans> = forward
+                .errors
+                .iter()
+                .skip(forward.errors.len().saturating_sub(4))
+                .map(|e| Spans::from(e.as_str()))
+                .collect();
+            Paragraph::new(lines).style(Style::default().fg(Color::Red))
+        }
+        Some(forward) => Paragraph::new(format!(
+            "localhost:{} -> {}:{}",
+            forward.local, forward.target, forward.remote
+        )),
+    };
+    f.render_widget(
+        details
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title("Details")),
+        chunks[1],
+    );
+}
+
+fn render_workload_table<B: Backend>(
+    f: &mut Frame<B>,
+    app: &mut App,
+    area: Rect,
+    kind: WorkloadKind,
+) {
+    let rows: Vec<Row> = app
+        .workloads
+        .iter()
+        .zip(filtered_names(app))
+        .map(|(w, name)| {
+            let color = if w.columns.iter().any(|c| c == "Failed") {
+                Color::Red
+            } else if w.healthy {
+                Color::Green
+            } else {
+                Color::Yellow
+            };
+            let mut cells = vec![Cell::from(name)];
+            cells.extend(w.columns.iter().enumerate().map(|(i, value)| {
+                let cell = Cell::from(value.clone());
+                // The first column is READY, COMPLETIONS or SCHEDULE.
+                if i == 0 && !w.suspended {
+                    cell.style(Style::default().fg(color))
+                } else {
+                    cell
+                }
+            }));
+            let row = Row::new(cells);
+            if w.suspended {
+                row.style(Style::default().fg(Color::DarkGray))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let mut headers = vec!["NAME"];
+    headers.extend(kind.headers());
+    let mut widths = vec![Constraint::Percentage(30)];
+    widths.extend(kind.headers().iter().map(|header| match *header {
+        "SCHEDULE" => Constraint::Length(14),
+        header => Constraint::Length(header.len().max(6) as u16 + 1),
+    }));
+    let title = format!("{} in {}", kind.title(), app.current_namespace());
+    let title = busy_title(app, &marked_title(&title, &app.marked));
+    render_resource_table(
+        f,
+        area,
+        title,
+        &headers,
+        rows,
+        &widths,
+        &mut app.workload_list_state,
+    );
+}
+
+/// Replica progress of the watched rollout above the status messages seen
+/// so far.
+fn render_rollout_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let Some(rollout) = &app.rollout else {
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(area);
+
+    let (ratio, label) = match &rollout.progress {
+        Some(d) if d.desired > 0 => (
+            (d.updated.min(d.available) as f64 / d.desired as f64).min(1.0),
+            format!("{} updated, {} available of {}", d.updated, d.available, d.desired),
+        ),
+        Some(_) => (1.0, "Scaled to 0".to_string()),
+        None => (0.0, "Waiting for status...".to_string()),
+    };
+    let color = if rollout.done { Color::Green } else { Color::Yellow };
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Rollout deployment/{}",
+            rollout.deployment
+        )))
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio)
+        .label(label);
+    f.render_widget(gauge, chunks[0]);
+
+    let visible = chunks[1].height.saturating_sub(2) as usize;
+    let mut lines: Vec<Spans> = rollout
+        .messages
+        .iter()
+        .skip(rollout.messages.len().saturating_sub(visible))
+        .map(|m| Spans::from(m.as_str()))
+        .collect();
+    if !rollout.done && lines.len() < visible {
+        lines.push(Spans::from(Span::styled(
+            format!("{} Waiting for rollout to finish", worker::spinner_frame(app.tick)),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let messages = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Status"));
+    f.render_widget(messages, chunks[1]);
+}
+
+fn pod_status_color(pod: &Pod) -> Color {
+    match pod.status.as_str() {
+        "Running" if pod.ready == pod.total => Color::Green,
+        "Running" | "Pending" | "ContainerCreating" | "PodInitializing" => Color::Yellow,
+        "Succeeded" | "Completed" => Color::Blue,
+        "Terminating" => Color::Magenta,
+        _ => Color::Red,
+    }
+}
+
+/// An info box above a single-line input with the cursor at its end.
+fn render_text_input<B: Backend>(f: &mut Frame<B>, area: Rect, info: &str, title: &str, value: &str) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(area);
+
+    let info = Paragraph::new(info).block(Block::default().borders(Borders::ALL));
+    f.render_widget(info, chunks[0]);
+
+    let input =
+        Paragraph::new(value).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(input, chunks[1]);
+
+    f.set_cursor(chunks[1].x + value.len() as u16 + 1, chunks[1].y + 1);
+}
+
+fn render_copy_pod_form<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let form = &app.copy_form;
+    let lines: Vec<Spans> = CopyField::ALL
+        .iter()
+        .map(|field| {
+            let line = format!("{:<18} {}", format!("{}:", field.label()), form.value(*field));
+            if *field == form.focused() {
+                Spans::from(Span::styled(
+                    format!("▶{}", line),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Spans::from(format!(" {}", line))
+            }
+        })
+        .collect();
+
+    let title = busy_title(app, &format!("Copy Pod {}", form.pod));
+    let panel = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(panel, area);
+
+    let focused = form.focused();
+    if focused.is_text() {
+        let value = form.value(focused);
+        let width = if focused == CopyField::Image && form.image.is_empty() {
+            0
+        } else {
+            value.chars().count()
+        };
+        f.set_cursor(
+            area.x + 21 + width as u16,
+            area.y + 1 + form.focus as u16,
+        );
+    }
+}
+
+fn render_output_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let output = Paragraph::new(app.output.as_str())
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Output"));
+    f.render_widget(output, area);
+}
+
+fn render_logs_panel<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let Some(logs) = app.logs.as_mut() else {
+        return;
+    };
+
+    logs.page_height = area.height.saturating_sub(2) as usize;
+    if logs.follow {
+        logs.scroll_bottom();
+    }
+
+    let lines: Vec<Spans> = logs
+        .visible_lines()
+        .map(|l| Spans::from(l.as_str()))
+        .collect();
+    let output = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(logs.title()));
+    f.render_widget(output, area);
+}
+
+fn render_describe_panel<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let Some(view) = app.describe.as_mut() else {
+        return;
+    };
+
+    view.page_height = area.height.saturating_sub(2) as usize;
+    view.keep_cursor_visible();
+
+    let lines: Vec<Spans> = view
+        .rows()
+        .iter()
+        .enumerate()
+        .skip(view.scroll)
+        .take(view.page_height)
+        .map(|(index, row)| {
+            let text = match row.section {
+                Some(section) if section.folded => format!(
+                    "▸ {}  (+{} lines)",
+                    row.text,
+                    section.end - section.start - 1
+                ),
+                Some(_) => format!("▾ {}", row.text),
+                None => format!("  {}", row.text),
+            };
+            let style = if index == view.cursor {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else if row.section.is_some() {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Spans::from(Span::styled(text, style))
+        })
+        .collect();
+
+    let panel = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(view.title()));
+    f.render_widget(panel, area);
+}
+
+fn render_manifest_panel<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let Some(title) = app.manifest.as_ref().map(|view| busy_title(app, &view.title())) else {
+        return;
+    };
+    let Some(view) = app.manifest.as_mut() else {
+        return;
+    };
+
+    view.page_height = area.height.saturating_sub(2) as usize;
+
+    // No wrapping: indentation is what makes YAML readable.
+    let panel = Paragraph::new(view.visible_spans())
+        .scroll((0, view.scroll_x))
+        .block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(panel, area);
+}
+
+fn render_edit_confirm_panel<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let Some(pending) = app.pending_edit.as_mut() else {
+        return;
+    };
+
+    pending.page_height = area.height.saturating_sub(2) as usize;
+
+    let lines: Vec<Spans> = pending
+        .diff
+        .iter()
+        .skip(pending.scroll)
+        .take(pending.page_height)
+        .map(|line| {
+            let color = match line {
+                l if l.starts_with("+++") || l.starts_with("---") => Color::White,
+                l if l.starts_with('+') => Color::Green,
+                l if l.starts_with('-') => Color::Red,
+                l if l.starts_with("@@") => Color::Cyan,
+                _ => Color::Reset,
+            };
+            Spans::from(Span::styled(line.as_str(), Style::default().fg(color)))
+        })
+        .collect();
+
+    let panel = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(pending.title()));
+    f.render_widget(panel, area);
+}
+
+/// A `width` x `height` rectangle in the middle of `area`, clamped to fit.
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// A modal asking to confirm an action, drawn over the screen it was opened
+/// from. Fields are `(label, value)` options below the body, one of which
+/// has the focus.
+struct ConfirmDialog<'a> {
+    title: String,
+    color: Color,
+    body: Vec<Spans<'a>>,
+    fields: Vec<(String, String)>,
+    focus: usize,
+}
+
+fn render_confirm_dialog<B: Backend>(f: &mut Frame<B>, dialog: ConfirmDialog, area: Rect) {
+    let label_width = dialog.fields.iter().map(|(l, _)| l.len()).max().unwrap_or(0) + 2;
+    let mut lines = dialog.body;
+    for (index, (label, value)) in dialog.fields.into_iter().enumerate() {
+        let style = if index == dialog.focus {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        lines.push(Spans::from(vec![
+            Span::raw(format!("{:<width$}", label, width = label_width)),
+            Span::styled(value, style),
+        ]));
+    }
+
+    let rect = centered_rect(60, lines.len() as u16 + 2, area);
+    f.render_widget(Clear, rect);
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(dialog.color))
+            .title(dialog.title),
+    );
+    f.render_widget(panel, rect);
+}
+
+/// The delete confirmation, drawn as a modal over the current screen.
+fn render_delete_dialog<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let Some(pending) = &app.pending_delete else {
+        return;
+    };
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+
+    let mut body = vec![Spans::from(Span::styled(
+        format!("Delete {}?", pending.target()),
+        bold.fg(Color::Red),
+    ))];
+    if pending.names.len() > 1 {
+        const SHOWN: usize = 5;
+        body.extend(pending.names.iter().take(SHOWN).map(|n| Spans::from(format!("  {}", n))));
+        if pending.names.len() > SHOWN {
+            body.push(Spans::from(format!("  (+{} more)", pending.names.len() - SHOWN)));
+        }
+    }
+    body.push(Spans::from(""));
+    if let Some(text) = &pending.confirm_text {
+        body.push(Spans::from(vec![
+            Span::raw("Type "),
+            Span::styled(text.as_str(), bold),
+            Span::raw(" to confirm:"),
+        ]));
+    }
+
+    let fields = pending
+        .fields()
+        .into_iter()
+        .map(|field| match field {
+            DeleteField::Confirm => ("Confirm".to_string(), format!("{}_", pending.typed)),
+            DeleteField::GracePeriod => {
+                ("Grace period".to_string(), format!("◀ {} ▶", pending.grace_display()))
+            }
+            DeleteField::Force => (
+                "Force".to_string(),
+                if pending.force { "[x]" } else { "[ ]" }.to_string(),
+            ),
+        })
+        .collect();
+
+    let title = match &app.selected_context {
+        Some(context) if pending.confirm_text.is_some() => {
+            format!("Confirm Delete ({} is production)", context)
+        }
+        _ => "Confirm Delete".to_string(),
+    };
+    let dialog = ConfirmDialog {
+        title,
+        color: Color::Red,
+        body,
+        fields,
+        focus: pending.focus,
+    };
+    render_confirm_dialog(f, dialog, area);
+}
+
+fn render_message_panel<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let message = Paragraph::new(app.message.as_str())
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Message"));
+    f.render_widget(message, area);
+}
+
+fn render_default_panel<B: Backend>(f: &mut Frame<B>, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title("Welcome");
+    f.render_widget(block, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::JobResult;
+    use crate::events::Event;
+    use crate::metrics::Usage;
+    use crate::nodes::Node;
+    use crate::secrets::{encode_base64, Secret};
+    use crate::kube::{fake::FakeKube, KubeBackend};
+    use crate::pods::Container;
+    use crate::resources::ApiResource;
+    use crate::workloads::{Deployment, Workload};
+    use std::{
+        collections::HashMap,
+        sync::Arc,
+        thread,
+        time::{Duration, Instant},
+    };
+    use tui::backend::TestBackend;
+
+    fn pod(name: &str, status: &str, restarts: u64) -> Pod {
+        Pod {
+            name: name.to_string(),
+            namespace: "default".to_string(),
+            status: status.to_string(),
+            ready: 1,
+            total: 1,
+            restarts,
+            created: None,
+            node: Some("node-1".to_string()),
+            ip: None,
+            owner: None,
+            labels: Default::default(),
+        }
+    }
+
+    fn owned_pod(name: &str, kind: &str, owner: &str) -> Pod {
+        Pod {
+            owner: Some((kind.to_string(), owner.to_string())),
+            ..pod(name, "Running", 0)
+        }
+    }
+
+    fn labelled_pod(name: &str, labels: &[(&str, &str)]) -> Pod {
+        Pod {
+            labels: labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..pod(name, "Running", 0)
+        }
+    }
+
+    fn container(name: &str, ephemeral: bool) -> Container {
+        Container {
+            name: name.to_string(),
+            ephemeral,
+        }
+    }
+
+    fn event(object: &str, event_type: &str, reason: &str, last_seen: i64) -> Event {
+        Event {
+            namespace: "default".to_string(),
+            kind: "Pod".to_string(),
+            object: object.to_string(),
+            event_type: event_type.to_string(),
+            reason: reason.to_string(),
+            message: format!("{} happened", reason),
+            count: 1,
+            last_seen: Some(last_seen),
+        }
+    }
+
+    fn node(name: &str, ready: bool) -> Node {
+        Node {
+            name: name.to_string(),
+            ready,
+            unschedulable: false,
+            roles: vec!["worker".to_string()],
+            version: "v1.30.2".to_string(),
+            cpu: "4".to_string(),
+            memory: "16318440Ki".to_string(),
+            taints: Vec::new(),
+            pods: 3,
+        }
+    }
+
+    fn usage(cpu_millis: u64, memory_mib: u64) -> Usage {
+        Usage {
+            cpu_millis,
+            memory_bytes: memory_mib << 20,
+        }
+    }
+
+    fn secret(name: &str, secret_type: &str, data: &[(&str, &str)]) -> Secret {
+        Secret {
+            name: name.to_string(),
+            secret_type: secret_type.to_string(),
+            data: data
+                .iter()
+                .map(|(k, v)| (k.to_string(), encode_base64(v.as_bytes())))
+                .collect(),
+            created: None,
+        }
+    }
+
+    fn fake_cluster() -> FakeKube {
+        FakeKube {
+            contexts: Some(vec!["dev".to_string(), "prod".to_string()]),
+            current_context: Some("dev".to_string()).into(),
+            namespaces: Some(vec!["default".to_string(), "payments".to_string()]),
+            pods: HashMap::from([
+                (
+                    "default".to_string(),
+                    vec![pod("web-2", "Running", 0), pod("api-1", "CrashLoopBackOff", 7)],
+                ),
+                ("payments".to_string(), vec![pod("billing-0", "Running", 1)]),
+            ]),
+            containers: HashMap::from([
+                ("api-1".to_string(), vec![container("api", false)]),
+                (
+                    "web-2".to_string(),
+                    vec![container("web", false), container("debugger", true)],
+                ),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn app_with(kube: FakeKube) -> (App, Arc<FakeKube>) {
+        let kube = Arc::new(kube);
+        (App::new(kube.clone()), kube)
+    }
+
+    /// Waits for every background job to finish and applies its result.
+    fn settle(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.worker.is_busy() {
+            assert!(Instant::now() < deadline, "background jobs did not finish");
+            app.poll_jobs();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        for key in keys {
+            handle_key(app, *key);
+            settle(app);
+        }
+    }
+
+    /// Moves the main menu highlight down to the command called `name`, the
+    /// way a user would, so previews on the way load as usual.
+    fn select_menu(app: &mut App, name: &str) {
+        let index = app
+            .commands
+            .iter()
+            .position(|c| c == name)
+            .unwrap_or_else(|| panic!("no menu command {:?}", name));
+        while app.list_state.selected() != Some(index) {
+            press(app, &[KeyCode::Down]);
+        }
+    }
+
+    const WIDTH: u16 = 80;
+    const HEIGHT: u16 = 16;
+
+    /// Renders the current screen into a `TestBackend` and returns it as lines
+    /// of text with trailing blanks removed.
+    fn render(app: &mut App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+        terminal.draw(|f| ui(f, app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..HEIGHT)
+            .map(|y| {
+                let line: String = (0..WIDTH).map(|x| buffer.get(x, y).symbol.as_str()).collect();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn initial_context_comes_from_backend() {
+        let (app, _) = app_with(fake_cluster());
+        assert_eq!(app.selected_context.as_deref(), Some("dev"));
+        assert!(app.state == AppState::MainMenu);
+    }
+
+    #[test]
+    fn choosing_a_namespace_scopes_the_pod_list() {
+        let (mut app, kube) = app_with(fake_cluster());
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        assert!(app.state == AppState::NamespaceSelection);
+        assert_eq!(app.namespaces, vec!["default", "payments"]);
+
+        press(&mut app, &[KeyCode::Char('j'), KeyCode::Enter]);
+        assert!(app.state == AppState::MainMenu);
+        assert_eq!(app.current_namespace(), "payments");
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        assert!(app.state == AppState::ExecPodSelection);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("billing-0"));
+        assert!(kube.calls().contains(&"list_pods payments".to_string()));
+    }
+
+    #[test]
+    fn switching_context_updates_backend_and_header() {
+        let (mut app, kube) = app_with(fake_cluster());
+
+        press(&mut app, &[KeyCode::Enter, KeyCode::Down, KeyCode::Enter]);
+        assert!(app.state == AppState::MainMenu);
+        assert_eq!(app.selected_context.as_deref(), Some("prod"));
+        assert_eq!(kube.current_context().as_deref(), Some("prod"));
+    }
+
+    #[test]
+    fn backend_errors_are_shown_as_messages() {
+        let (mut app, _) = app_with(FakeKube {
+            namespaces: None,
+            ..fake_cluster()
+        });
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        assert!(app.state == AppState::Message);
+        assert!(app.message.contains("Error loading namespaces"));
+
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::MainMenu);
+    }
+
+    #[test]
+    fn pod_list_is_sorted_and_wraps_around() {
+        let (mut app, _) = app_with(fake_cluster());
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+        let names: Vec<&str> = app.pods.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["api-1", "web-2"]);
+
+        press(&mut app, &[KeyCode::Up]);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-1"));
+        press(&mut app, &[KeyCode::Down, KeyCode::Down]);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-1"));
+
+        press(&mut app, &[KeyCode::Char('s'), KeyCode::Char('s'), KeyCode::Char('S')]);
+        assert!(app.pod_sort == PodSortColumn::Restarts);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-1"));
+        assert_eq!(app.pods[0].name, "api-1");
+    }
+
+    #[test]
+    fn watched_pod_list_refreshes_in_place() {
+        let (mut app, kube) = app_with(fake_cluster());
+        app.watch.interval = Some(Duration::ZERO);
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter, KeyCode::Down]);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("web-2"));
+        app.poll_watch();
+        settle(&mut app);
+        let loads = kube.calls().iter().filter(|c| *c == "list_pods default").count();
+        assert_eq!(loads, 2);
+        assert!(app.watch.changes.is_empty());
+
+        // A refresh that adds a pod ahead of the highlighted one.
+        app.worker.spawn("Refreshing pods", || {
+            JobResult::Pods(
+                AppState::ExecPodSelection,
+                Ok(vec![
+                    pod("web-2", "Terminating", 0),
+                    pod("api-1", "CrashLoopBackOff", 8),
+                    pod("api-0", "Running", 0),
+                ]),
+            )
+        });
+        settle(&mut app);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("web-2"));
+        assert_eq!(app.pod_list_state.selected(), Some(2));
+        assert_eq!(app.row_change("api-0"), Some(RowChange::Added));
+        assert_eq!(app.row_change("api-1"), Some(RowChange::Changed));
+
+        app.watch.interval = None;
+        app.poll_watch();
+        assert!(!app.worker.is_busy());
+
+        // Esc leaves the list even while a refresh is running.
+        app.load_pods(AppState::ExecPodSelection);
+        handle_key(&mut app, KeyCode::Esc);
+        assert!(app.state == AppState::MainMenu);
+    }
+
+    #[test]
+    fn copy_pod_form_builds_debug_copy_args() {
+        let (mut app, _) = app_with(FakeKube {
+            outputs: HashMap::from([(
+                "debug -n default api-1 --copy-to=api-1-debug --container=api --image=busybox \
+                 --share-processes=false --same-node"
+                    .to_string(),
+                "pod/api-1-debug created".to_string(),
+            )]),
+            ..fake_cluster()
+        });
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+        press(&mut app, &[KeyCode::Enter, KeyCode::Enter]);
+        assert!(app.state == AppState::CopyPodForm);
+        assert_eq!(app.copy_form.new_name, "api-1-debug");
+
+        press(&mut app, &[KeyCode::Tab, KeyCode::Tab]);
+        let keys: Vec<KeyCode> = "busybox".chars().map(KeyCode::Char).collect();
+        press(&mut app, &keys);
+        press(&mut app, &[KeyCode::Tab, KeyCode::Tab, KeyCode::Char(' ')]);
+        press(&mut app, &[KeyCode::Tab, KeyCode::Right, KeyCode::Tab, KeyCode::Left]);
+        press(&mut app, &[KeyCode::Enter]);
+
+        assert!(app.state == AppState::ShowOutput);
+        assert_eq!(app.output, "pod/api-1-debug created");
+    }
+
+    #[test]
+    fn copy_pod_form_stays_open_without_a_name() {
+        let (mut app, kube) = app_with(fake_cluster());
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+        press(&mut app, &[KeyCode::Enter, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Backspace; 11]);
+        press(&mut app, &[KeyCode::Enter]);
+
+        assert!(app.state == AppState::CopyPodForm);
+        assert_eq!(app.status_message.as_deref(), Some("Please enter a new pod name"));
+        assert!(!kube.calls().iter().any(|call| call.contains("--copy-to")));
+    }
+
+    #[test]
+    fn copy_pod_attaches_to_the_copy() {
+        let (mut app, kube) = app_with(fake_cluster());
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+        press(&mut app, &[KeyCode::Enter, KeyCode::Enter, KeyCode::Enter]);
+        let session = app.interactive.take().expect("attach should be requested");
+        app.run_interactive(session);
+
+        assert!(app.state == AppState::PodSelection);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Debug session in api-1-debug exited with code 0")
+        );
+        assert!(kube.calls().contains(
+            &"run_attached debug -n default api-1 --copy-to=api-1-debug --container=api \
+              --share-processes=true -it"
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn debug_pod_attaches_an_ephemeral_container() {
+        let (mut app, kube) = app_with(fake_cluster());
+        app.debug_images = vec!["busybox".to_string(), "nicolaka/netshoot".to_string()];
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+        press(&mut app, &[KeyCode::Enter, KeyCode::Down, KeyCode::Enter]);
+        // The ephemeral "debugger" container is not a valid target, leaving only "web".
+        assert!(app.state == AppState::DebugImageSelection);
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        let session = app.interactive.take().expect("attach should be requested");
+        app.run_interactive(session);
+
+        assert!(app.state == AppState::DebugPodSelection);
+        assert!(kube.calls().contains(
+            &"run_attached debug -it -n default web-2 --image=nicolaka/netshoot --target=web"
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn describe_folds_sections_and_returns_to_the_list() {
+        let output = "Name:         api-1\n\
+                      Labels:       app=api\n              tier=backend\n\
+                      Containers:\n  api:\n    Image:  api:1.0\n\
+                      Events:       <none>\n";
+        let (mut app, kube) = app_with(FakeKube {
+            outputs: HashMap::from([(
+                "describe pod api-1 -n default".to_string(),
+                output.to_string(),
+            )]),
+            ..fake_cluster()
+        });
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Char('d')]);
+        assert!(app.state == AppState::Describe);
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter, KeyCode::Char('j'), KeyCode::Enter]);
+        let screen = render(&mut app);
+        assert!(screen[4].contains("Describe pod/api-1"));
+        assert!(screen[6].contains("▸ Labels:       app=api  (+1 lines)"));
+        assert!(screen[7].contains("▸ Containers:  (+2 lines)"));
+        assert!(screen[8].contains("  Events:       <none>"));
+
+        press(&mut app, &[KeyCode::Esc, KeyCode::Esc, KeyCode::Up, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Char('d')]);
+        assert!(app.state == AppState::Describe);
+        assert!(kube.calls().contains(&"run describe namespace default".to_string()));
+    }
+
+    #[test]
+    fn manifest_hides_noise_and_searches() {
+        let yaml = "apiVersion: v1\n\
+                    kind: Pod\n\
+                    metadata:\n  \
+                      managedFields:\n  \
+                      - manager: kubectl\n    \
+                        operation: Update\n  \
+                      name: api-1\n\
+                    spec:\n  \
+                      containers:\n  \
+                      - image: api:1.0\n    \
+                        name: api\n\
+                    status:\n  \
+                      phase: Running\n";
+        let (mut app, _) = app_with(FakeKube {
+            outputs: HashMap::from([(
+                "get pod api-1 -o yaml -n default".to_string(),
+                yaml.to_string(),
+            )]),
+            ..fake_cluster()
+        });
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Char('y')]);
+        assert!(app.state == AppState::Manifest);
+
+        let screen = render(&mut app);
+        assert!(screen[5].ends_with("││ 1 apiVersion: v1                                    │"));
+        assert!(screen[8].ends_with("││ 7   name: api-1                                     │"));
+        assert!(!screen[5..]
+            .iter()
+            .any(|l| l.contains("managedFields") || l.contains("phase")));
+
+        let keys: Vec<KeyCode> = "/API".chars().map(KeyCode::Char).collect();
+        press(&mut app, &keys);
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(render(&mut app)[4].contains("/API [1/4]"));
+
+        press(&mut app, &[KeyCode::Char('h')]);
+        assert!(render(&mut app)[8].contains("4   managedFields:"));
+
+        press(&mut app, &[KeyCode::Char('q'), KeyCode::Esc]);
+        assert!(app.state == AppState::ExecPodSelection);
+    }
+
+    #[test]
+    fn edit_diffs_and_applies_after_confirmation() {
+        let yaml = "metadata:\n  managedFields:\n  - manager: kubectl\n  name: api-1\n\
+                    spec:\n  containers:\n  - image: api:1.0\n";
+        let (mut app, kube) = app_with(FakeKube {
+            outputs: HashMap::from([
+                ("get pod api-1 -o yaml -n default".to_string(), yaml.to_string()),
+                (
+                    "diff".to_string(),
+                    "-  - image: api:1.0\n+  - image: api:2.0\n".to_string(),
+                ),
+            ]),
+            ..fake_cluster()
+        });
+        app.editor = "sed -i s/api:1.0/api:2.0/".to_string();
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Char('y'), KeyCode::Char('e')]);
+        let session = app.interactive.take().expect("edit should be requested");
+        app.run_interactive(session);
+        assert!(render(&mut app)[4].contains("Diffing..."));
+        settle(&mut app);
+
+        assert!(app.state == AppState::EditConfirm);
+        let path = app.pending_edit.as_ref().unwrap().path.clone();
+        let edited = std::fs::read_to_string(&path).unwrap();
+        assert!(edited.contains("image: api:2.0"));
+        assert!(!edited.contains("managedFields"));
+        assert!(render(&mut app)[4].contains("Apply changes to pod/api-1?"));
+
+        // Esc neither cancels the apply nor hides its outcome.
+        handle_key(&mut app, KeyCode::Char('y'));
+        assert!(app.state == AppState::Applying);
+        handle_key(&mut app, KeyCode::Esc);
+        assert!(app.state == AppState::Applying);
+        settle(&mut app);
+        assert!(app.state == AppState::ShowOutput);
+        assert!(kube
+            .calls()
+            .contains(&format!("run apply -f {}", path.display())));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn resource_browser_lists_describes_and_deletes() {
+        let (mut app, kube) = app_with(FakeKube {
+            api_resources: vec![
+                ApiResource {
+                    name: "deployments.apps".to_string(),
+                    namespaced: true,
+                },
+                ApiResource {
+                    name: "nodes".to_string(),
+                    namespaced: false,
+                },
+            ],
+            resources: HashMap::from([
+                ("deployments.apps default".to_string(), vec!["web".to_string()]),
+                ("nodes".to_string(), vec!["node-1".to_string()]),
+            ]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(5));
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::ResourceKindSelection);
+        assert!(render(&mut app)
+            .iter()
+            .any(|line| line.contains("nodes (cluster)")));
+
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::ResourceList);
+        assert_eq!(app.resources, vec!["web"]);
+
+        press(&mut app, &[KeyCode::Char('x')]);
+        assert!(app.state == AppState::ConfirmDelete);
+        press(&mut app, &[KeyCode::Char('y')]);
+        assert!(app.state == AppState::ResourceList);
+        let calls = kube.calls();
+        assert!(calls.contains(&"run delete deployments.apps web -n default".to_string()));
+        assert_eq!(
+            calls
+                .iter()
+                .filter(|c| *c == "list_resources deployments.apps default")
+                .count(),
+            2
+        );
+
+        press(&mut app, &[KeyCode::Esc, KeyCode::Down, KeyCode::Enter]);
+        assert_eq!(app.resources, vec!["node-1"]);
+        press(&mut app, &[KeyCode::Char('d')]);
+        assert!(app.state == AppState::Describe);
+        assert!(kube.calls().contains(&"run describe nodes node-1".to_string()));
+    }
+
+    fn deployment(name: &str, desired: u64, updated: u64, available: u64) -> Deployment {
+        Deployment {
+            name: name.to_string(),
+            desired,
+            ready: available,
+            updated,
+            available,
+            created: None,
+        }
+    }
+
+    #[test]
+    fn deployments_scale_restart_and_roll_back() {
+        let (mut app, kube) = app_with(FakeKube {
+            deployments: HashMap::from([(
+                "default".to_string(),
+                vec![deployment("web", 3, 3, 3)],
+            )]),
+            outputs: HashMap::from([(
+                "rollout history deployment/web -n default".to_string(),
+                "deployment.apps/web\nREVISION  CHANGE-CAUSE\n1         <none>\n2         <none>\n"
+                    .to_string(),
+            )]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(6));
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::DeploymentList);
+        assert!(render(&mut app).iter().any(|line| line.contains("web") && line.contains("3/3")));
+
+        press(&mut app, &[KeyCode::Char('s')]);
+        assert!(app.state == AppState::ScaleInput);
+        assert_eq!(app.scale_input, "3");
+        press(&mut app, &[KeyCode::Backspace, KeyCode::Char('x'), KeyCode::Char('5')]);
+        assert_eq!(app.scale_input, "5");
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::DeploymentList);
+
+        press(&mut app, &[KeyCode::Char('r'), KeyCode::Char('u')]);
+        assert!(app.state == AppState::RevisionSelection);
+        assert_eq!(app.revisions.iter().map(|r| r.number).collect::<Vec<_>>(), vec![2, 1]);
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        assert!(app.state == AppState::DeploymentList);
+
+        let calls = kube.calls();
+        for call in [
+            "run scale deployment/web --replicas=5 -n default",
+            "run rollout restart deployment/web -n default",
+            "run rollout undo deployment/web --to-revision=1 -n default",
+        ] {
+            assert!(calls.contains(&call.to_string()), "missing {}", call);
+        }
+    }
+
+    #[test]
+    fn rollout_status_is_polled_until_complete() {
+        let (mut app, kube) = app_with(FakeKube {
+            deployments: HashMap::from([(
+                "default".to_string(),
+                vec![deployment("web", 2, 1, 1)],
+            )]),
+            outputs: HashMap::from([(
+                "rollout status deployment/web -n default --watch=false".to_string(),
+                "Waiting for deployment \"web\" rollout to finish: 1 of 2 updated replicas are available...\n"
+                    .to_string(),
+            )]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(6));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('w')]);
+        settle(&mut app);
+        assert!(app.state == AppState::RolloutStatus);
+        let rollout = app.rollout.as_ref().unwrap();
+        assert!(!rollout.done);
+        assert_eq!(rollout.messages.len(), 1);
+        assert!(render(&mut app)
+            .iter()
+            .any(|line| line.contains("1 updated, 1 available of 2")));
+
+        // Polling waits for the next interval.
+        app.poll_rollout();
+        assert!(!app.worker.is_busy());
+        app.tick += 10;
+        app.poll_rollout();
+        settle(&mut app);
+        assert_eq!(app.rollout.as_ref().unwrap().messages.len(), 1);
+
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.state == AppState::DeploymentList);
+        assert!(app.rollout.is_none());
+        assert_eq!(
+            kube.calls()
+                .iter()
+                .filter(|c| c.starts_with("run rollout status"))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn marked_deployments_restart_and_delete_in_bulk() {
+        let (mut app, kube) = app_with(FakeKube {
+            deployments: HashMap::from([(
+                "default".to_string(),
+                vec![
+                    deployment("api", 2, 2, 2),
+                    deployment("web", 3, 3, 3),
+                    deployment("worker", 1, 1, 1),
+                ],
+            )]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(6));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char(' '), KeyCode::Char(' ')]);
+        assert_eq!(app.targets(), vec!["api", "web"]);
+        let screen = render(&mut app);
+        assert!(screen.iter().any(|line| line.contains("Deployments in default (2 marked)")));
+        assert!(screen.iter().any(|line| line.contains("● web")));
+
+        press(&mut app, &[KeyCode::Char('r')]);
+        assert!(app.state == AppState::BulkSummary);
+        assert_eq!(app.bulk.as_ref().unwrap().title(), "Restart deployment: 2 succeeded, 0 failed");
+        assert!(app.marked.is_empty());
+
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('i'), KeyCode::Char('x')]);
+        assert!(app.state == AppState::ConfirmDelete);
+        assert_eq!(app.pending_delete.as_ref().unwrap().names.len(), 3);
+        press(&mut app, &[KeyCode::Esc, KeyCode::Esc]);
+        assert!(app.marked.is_empty());
+        assert!(app.state == AppState::DeploymentList);
+
+        let calls = kube.calls();
+        for name in ["api", "web"] {
+            let call = format!("run rollout restart deployment/{} -n default", name);
+            assert!(calls.contains(&call), "missing {}", call);
+        }
+        assert!(!calls.iter().any(|c| c.contains("deployment/worker")));
+    }
+
+    fn workload(name: &str, finished: bool, suspended: bool) -> Workload {
+        Workload {
+            name: name.to_string(),
+            columns: vec!["1/1".to_string(), "Complete".to_string(), "1d".to_string()],
+            healthy: finished,
+            finished,
+            suspended,
+        }
+    }
+
+    #[test]
+    fn cronjobs_are_suspended_and_triggered() {
+        let (mut app, kube) = app_with(FakeKube {
+            workloads: HashMap::from([(
+                "cronjob default".to_string(),
+                vec![workload("backup", false, true)],
+            )]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(10));
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::CronJobList);
+        assert!(render(&mut app).iter().any(|line| line.contains("SCHEDULE")));
+
+        press(&mut app, &[KeyCode::Char('p'), KeyCode::Char('t')]);
+        let calls = kube.calls();
+        assert!(calls.contains(
+            &r#"run patch cronjob/backup --type=merge -p {"spec":{"suspend":false}} -n default"#
+                .to_string()
+        ));
+        assert!(calls
+            .iter()
+            .any(|c| c.starts_with("run create job backup-manual-")
+                && c.ends_with(" --from=cronjob/backup -n default")));
+        assert_eq!(
+            calls.iter().filter(|c| *c == "list_workloads cronjob default").count(),
+            3
+        );
+    }
+
+    #[test]
+    fn finished_jobs_are_deleted_together() {
+        let (mut app, kube) = app_with(FakeKube {
+            workloads: HashMap::from([(
+                "job default".to_string(),
+                vec![
+                    workload("migrate", true, false),
+                    workload("report", false, false),
+                    workload("seed", true, false),
+                ],
+            )]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(9));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('c')]);
+        assert!(app.state == AppState::ConfirmDelete);
+        assert!(render(&mut app).iter().any(|line| line.contains("Delete 2 job resources?")));
+
+        press(&mut app, &[KeyCode::Char('y')]);
+        assert!(app.state == AppState::BulkSummary);
+        let calls = kube.calls();
+        assert!(calls.contains(&"run delete job migrate -n default".to_string()));
+        assert!(calls.contains(&"run delete job seed -n default".to_string()));
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::JobList);
+    }
+
+    #[test]
+    fn port_forwards_report_status_and_stop() {
+        let (mut app, kube) = app_with(FakeKube {
+            scripts: HashMap::from([
+                (
+                    "port-forward -n default pod/api-1 8080:80".to_string(),
+                    "echo 'Forwarding from 127.0.0.1:8080 -> 80'; exec sleep 60".to_string(),
+                ),
+                (
+                    "port-forward -n default pod/web-2 9090:9090".to_string(),
+                    "echo 'unable to listen on port 9090' >&2; exit 1".to_string(),
+                ),
+            ]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(2));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('f')]);
+        assert!(app.state == AppState::PortForwardInput);
+        for c in "8080:80".chars() {
+            press(&mut app, &[KeyCode::Char(c)]);
+        }
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::ExecPodSelection);
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Char('f'), KeyCode::Char('x')]);
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::PortForwardInput);
+        assert_eq!(app.status_message.as_deref(), Some("Invalid port: \"\""));
+        for c in "9090".chars() {
+            press(&mut app, &[KeyCode::Char(c)]);
+        }
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(kube
+            .calls()
+            .contains(&"spawn port-forward -n default pod/web-2 9090:9090".to_string()));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.port_forwards.iter().any(|f| f.status == ForwardStatus::Starting) {
+            assert!(Instant::now() < deadline, "port forwards did not start");
+            app.poll_port_forwards();
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(app.port_forwards[0].status, ForwardStatus::Active);
+        assert_eq!(app.port_forwards[1].status, ForwardStatus::Failed);
+
+        press(&mut app, &[KeyCode::Esc, KeyCode::Esc]);
+        app.list_state.select(Some(11));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Down]);
+        assert!(app.state == AppState::PortForwardList);
+        assert!(render(&mut app)
+            .iter()
+            .any(|line| line.contains("unable to listen on port 9090")));
+
+        press(&mut app, &[KeyCode::Char('x')]);
+        assert_eq!(app.port_forwards.len(), 1);
+        assert_eq!(app.port_forwards[0].target, "pod/api-1");
+    }
+
+    #[test]
+    fn deleting_a_pod_offers_grace_period_and_force() {
+        let (mut app, kube) = app_with(fake_cluster());
+
+        app.list_state.select(Some(2));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('x')]);
+        assert!(app.state == AppState::ConfirmDelete);
+        let screen = render(&mut app);
+        assert!(screen.iter().any(|line| line.contains("Delete pod/api-1?")));
+        // The pod list stays visible around the dialog.
+        assert!(screen.iter().any(|line| line.contains("Pods")));
+
+        press(&mut app, &[KeyCode::Right, KeyCode::Right, KeyCode::Char('y')]);
+        assert!(app.state == AppState::ExecPodSelection);
+        assert!(kube
+            .calls()
+            .contains(&"run delete pod api-1 --grace-period=5 -n default".to_string()));
+        assert_eq!(kube.calls().iter().filter(|c| *c == "list_pods default").count(), 2);
+    }
+
+    #[test]
+    fn production_deletes_require_typing_the_name() {
+        let (mut app, kube) = app_with(fake_cluster());
+        app.switch_context("prod").unwrap();
+
+        app.list_state.select(Some(2));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Down, KeyCode::Char('x')]);
+        assert!(app.is_text_input());
+        assert!(render(&mut app)
+            .iter()
+            .any(|line| line.contains("prod is production")));
+
+        press(&mut app, &[KeyCode::Char('y'), KeyCode::Enter]);
+        assert!(app.state == AppState::ConfirmDelete);
+        assert_eq!(app.status_message.as_deref(), Some("Type web-2 to confirm"));
+
+        press(&mut app, &[KeyCode::Backspace]);
+        for c in "web-2".chars() {
+            press(&mut app, &[KeyCode::Char(c)]);
+        }
+        press(&mut app, &[KeyCode::Tab, KeyCode::Tab, KeyCode::Char(' '), KeyCode::Enter]);
+        assert!(app.state == AppState::ExecPodSelection);
+        assert!(kube
+            .calls()
+            .contains(&"run delete pod web-2 --grace-period=0 --force -n default".to_string()));
+    }
+
+    #[test]
+    fn marked_pods_are_handled_in_bulk() {
+        let owner_query = "get replicaset web-7d4 -n default -o jsonpath=\
+            {.metadata.ownerReferences[0].kind}/{.metadata.ownerReferences[0].name}";
+        let (mut app, kube) = app_with(FakeKube {
+            pods: HashMap::from([(
+                "default".to_string(),
+                vec![
+                    owned_pod("web-7d4-a", "ReplicaSet", "web-7d4"),
+                    owned_pod("web-7d4-b", "ReplicaSet", "web-7d4"),
+                    owned_pod("migrate-x", "Job", "migrate"),
+                ],
+            )]),
+            outputs: HashMap::from([
+                (owner_query.to_string(), "Deployment/web".to_string()),
+                (
+                    "logs --tail=100 --all-containers=true -n default web-7d4-a".to_string(),
+                    "listening on :80\n".to_string(),
+                ),
+            ]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(2));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('a')]);
+        assert_eq!(app.marked.len(), 3);
+        press(&mut app, &[KeyCode::Char(' '), KeyCode::Char('i')]);
+        assert_eq!(app.targets(), vec!["migrate-x"]);
+        press(&mut app, &[KeyCode::Char('i')]);
+        assert_eq!(app.targets(), vec!["web-7d4-a", "web-7d4-b"]);
+        assert!(render(&mut app).iter().any(|line| line.contains("● web-7d4-a")));
+
+        press(&mut app, &[KeyCode::Char('l')]);
+        assert!(app.state == AppState::Describe);
+        let view = app.describe.as_ref().unwrap();
+        assert_eq!(view.title(), "Logs pod/web-7d4-a, web-7d4-b");
+        assert_eq!(view.sections.len(), 2);
+        assert!(view.lines.contains(&"  listening on :80".to_string()));
+
+        press(&mut app, &[KeyCode::Esc, KeyCode::Up, KeyCode::Char(' '), KeyCode::Char('r')]);
+        assert!(app.state == AppState::BulkSummary);
+        let summary = app.bulk.as_ref().unwrap();
+        assert_eq!(summary.title(), "Restart owners: 2 succeeded, 1 failed");
+        assert_eq!(
+            summary.results[2].1,
+            Ok("deployment/web already restarted".to_string())
+        );
+        assert_eq!(
+            kube.calls()
+                .iter()
+                .filter(|c| *c == "run rollout restart deployment/web -n default")
+                .count(),
+            1
+        );
+        assert!(render(&mut app).iter().any(|line| line.contains("✗ migrate-x")));
+
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::ExecPodSelection);
+        assert!(app.marked.is_empty());
+    }
+
+    fn event_cluster() -> FakeKube {
+        let mut kube = fake_cluster();
+        kube.events.insert(
+            "default".to_string(),
+            vec![
+                event("web-2", "Normal", "Pulled", 100),
+                event("api-1", "Warning", "BackOff", 300),
+                event("web-2", "Normal", "Started", 200),
+            ],
+        );
+        kube
+    }
+
+    #[test]
+    fn main_menu_previews_recent_warnings() {
+        let (mut app, _) = app_with(event_cluster());
+
+        select_menu(&mut app, "Events");
+        assert!(app.output.contains("pod/api-1  BackOff: BackOff happened"));
+        assert!(!app.output.contains("Pulled"));
+    }
+
+    #[test]
+    fn events_are_listed_newest_first() {
+        let (mut app, kube) = app_with(event_cluster());
+
+        select_menu(&mut app, "Events");
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::EventList);
+        let reasons: Vec<&str> = app.events.iter().map(|e| e.reason.as_str()).collect();
+        assert_eq!(reasons, ["BackOff", "Started", "Pulled"]);
+        assert!(kube.calls().contains(&"list_events default".to_string()));
+    }
+
+    #[test]
+    fn events_narrow_to_an_object_and_widen_to_all_namespaces() {
+        let (mut app, kube) = app_with(event_cluster());
+
+        select_menu(&mut app, "Events");
+        press(&mut app, &[KeyCode::Enter, KeyCode::Down, KeyCode::Char('o')]);
+        assert_eq!(app.visible_events().len(), 2);
+        assert!(render(&mut app).iter().any(|line| line.contains("for pod/web-2")));
+
+        press(&mut app, &[KeyCode::Char('o'), KeyCode::Char('a')]);
+        assert_eq!(app.visible_events().len(), 3);
+        assert!(kube.calls().contains(&"list_events --all-namespaces".to_string()));
+
+        press(&mut app, &[KeyCode::Char('a'), KeyCode::Esc]);
+        assert!(app.state == AppState::MainMenu);
+    }
+
+    #[test]
+    fn pod_list_opens_the_events_of_the_highlighted_pod() {
+        let (mut app, _) = app_with(event_cluster());
+
+        select_menu(&mut app, "Pods");
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('e')]);
+        assert!(app.state == AppState::EventList);
+        let reasons: Vec<&str> = app.visible_events().iter().map(|e| e.reason.as_str()).collect();
+        assert_eq!(reasons, ["BackOff"]);
+
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.state == AppState::ExecPodSelection);
+    }
+
+    fn node_cluster() -> FakeKube {
+        let mut kube = fake_cluster();
+        kube.nodes = vec![node("node-1", true), node("node-2", false)];
+        kube.pods.get_mut("payments").unwrap()[0].namespace = "payments".to_string();
+        kube.containers.insert("billing-0".to_string(), vec![container("billing", false)]);
+        kube
+    }
+
+    #[test]
+    fn nodes_are_listed_and_cordoned() {
+        let (mut app, kube) = app_with(node_cluster());
+
+        select_menu(&mut app, "Nodes");
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::NodeList);
+        let screen = render(&mut app);
+        assert!(screen.iter().any(|line| line.contains("NotReady")));
+        assert_eq!(app.nodes[0].memory_display(), "15.6Gi");
+
+        press(&mut app, &[KeyCode::Char('c')]);
+        assert!(kube.calls().contains(&"run cordon node-1".to_string()));
+    }
+
+    #[test]
+    fn node_pods_span_namespaces() {
+        let (mut app, kube) = app_with(node_cluster());
+
+        select_menu(&mut app, "Nodes");
+        press(&mut app, &[KeyCode::Enter, KeyCode::Enter]);
+        assert!(app.state == AppState::NodePodList);
+        assert!(kube.calls().contains(&"list_node_pods node-1".to_string()));
+        let pods: Vec<&str> = app.pods.iter().map(|p| p.namespace.as_str()).collect();
+        assert_eq!(pods, ["default", "payments", "default"]);
+        assert!(render(&mut app).iter().any(|line| line.contains("Pods on node-1")));
+        press(&mut app, &[KeyCode::Down, KeyCode::Char('d')]);
+        assert!(kube.calls().contains(&"run describe pod billing-0 -n payments".to_string()));
+
+        // Events of a pod in another namespace look beyond the current one.
+        press(&mut app, &[KeyCode::Esc, KeyCode::Char('e')]);
+        assert!(app.state == AppState::EventList);
+        assert!(app.events_all_namespaces);
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.state == AppState::NodePodList);
+        assert!(!app.events_all_namespaces);
+    }
+
+    #[test]
+    fn node_pods_are_handled_in_their_own_namespaces() {
+        let (mut app, kube) = app_with(node_cluster());
+
+        select_menu(&mut app, "Nodes");
+        press(&mut app, &[KeyCode::Enter, KeyCode::Enter, KeyCode::Down, KeyCode::Char('l')]);
+        assert!(app.state == AppState::PodLogs);
+        assert!(kube.calls().contains(&"list_containers payments billing-0".to_string()));
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.state == AppState::NodePodList);
+
+        press(&mut app, &[KeyCode::Char(' '), KeyCode::Char(' '), KeyCode::Char('d')]);
+        assert!(app.state == AppState::Describe);
+        let calls = kube.calls();
+        assert!(calls.contains(&"run describe pod web-2 -n default".to_string()));
+        assert!(calls.contains(&"run describe pod billing-0 -n payments".to_string()));
+
+        press(&mut app, &[KeyCode::Esc, KeyCode::Char('x'), KeyCode::Char('y')]);
+        assert!(app.state == AppState::BulkSummary);
+        let calls = kube.calls();
+        assert!(calls.contains(&"run delete pod web-2 -n default".to_string()));
+        assert!(calls.contains(&"run delete pod billing-0 -n payments".to_string()));
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.state == AppState::NodePodList);
+        assert!(app.marked.is_empty());
+
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.state == AppState::NodeList);
+    }
+
+    #[test]
+    fn draining_a_node_follows_its_evictions() {
+        let mut kube = node_cluster();
+        kube.scripts.insert(
+            "drain node-1 --ignore-daemonsets --delete-emptydir-data".to_string(),
+            "echo 'evicting pod default/web-2'; echo 'pod/web-2 evicted'".to_string(),
+        );
+        let (mut app, _) = app_with(kube);
+
+        select_menu(&mut app, "Nodes");
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('D')]);
+        assert!(app.state == AppState::DrainOptions);
+        press(&mut app, &[KeyCode::Down, KeyCode::Char(' '), KeyCode::Enter]);
+        assert!(app.state == AppState::DrainProgress);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.drain.as_ref().is_some_and(|d| d.is_running()) {
+            assert!(Instant::now() < deadline, "drain did not finish");
+            app.poll_drain();
+            thread::sleep(Duration::from_millis(1));
+        }
+        let drain = app.drain.as_ref().unwrap();
+        assert_eq!(drain.status, DrainStatus::Succeeded);
+        assert_eq!(drain.evicting, ["default/web-2"]);
+        assert_eq!(drain.evicted, ["web-2"]);
+        assert!(render(&mut app).iter().any(|line| line.contains("1 of 1 pods evicted")));
+
+        press(&mut app, &[KeyCode::Esc]);
+        settle(&mut app);
+        assert!(app.state == AppState::NodeList);
+        assert_eq!(app.status_message.as_deref(), Some("Drained node-1"));
+    }
+
+    #[test]
+    fn pod_usage_keeps_a_history() {
+        let (mut app, _) = app_with(FakeKube {
+            pod_metrics: Some(HashMap::from([("default/api-1".to_string(), usage(100, 64))])),
+            ..fake_cluster()
+        });
+        select_menu(&mut app, "Pods");
+        press(&mut app, &[KeyCode::Enter]);
+        let refreshed = HashMap::from([("default/api-1".to_string(), usage(200, 80))]);
+        app.worker.spawn("Loading metrics", || JobResult::PodMetrics(None, Ok(refreshed)));
+        settle(&mut app);
+        assert_eq!(app.pod_sparkline(&app.pods[0]), "▄█");
+        assert_eq!(app.pod_usage(&app.pods[0]).unwrap().memory_display(), "80Mi");
+        assert_eq!(app.pod_usage(&app.pods[1]), None);
+    }
+
+    #[test]
+    fn missing_metrics_are_reported_once_and_back_off() {
+        let (mut app, kube) = app_with(fake_cluster());
+        select_menu(&mut app, "Pods");
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::ExecPodSelection);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Usage columns unavailable: Failed to get pod metrics: error: Metrics API not available")
+        );
+        press(&mut app, &[KeyCode::Down]);
+        app.load_pods(AppState::ExecPodSelection);
+        settle(&mut app);
+        assert_eq!(app.status_message, None);
+        let tops = kube.calls().iter().filter(|c| c.starts_with("top_pods")).count();
+        assert_eq!(tops, 1);
+    }
+
+    const CERTIFICATE: &str = "
+        -----BEGIN CERTIFICATE-----
+        MIIBrzCCAVWgAwIBAgIUYE1SeHDo6X/KnHTPfFgMIqWV9eswCgYIKoZIzj0EAwIw
+        LDEQMA4GA1UECgwHRXhhbXBsZTEYMBYGA1UEAwwPd2ViLmV4YW1wbGUuY29tMCAX
+        DTI2MTAxNjE5NTYxMloYDzIxMjYwOTIyMTk1NjEyWjAsMRAwDgYDVQQKDAdFeGFt
+        cGxlMRgwFgYDVQQDDA93ZWIuZXhhbXBsZS5jb20wWTATBgcqhkjOPQIBBggqhkjO
+        PQMBBwNCAASjASKMCIeLr6hHlz9B8DTGfr9hJ3TvgwhiDZD7cCajg+hk51cl1Y/L
+        1UehVtAdr1UQShYuxl1PPQXsYmlYNRE9o1MwUTAdBgNVHQ4EFgQU7kQypxTqhrMw
+        G+aZaKgOrVqpmwQwHwYDVR0jBBgwFoAU7kQypxTqhrMwG+aZaKgOrVqpmwQwDwYD
+        VR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiB/2AytjB3dOQRiryXJrUhi
+        mxFdcwPiFt3ilI7pGgYFLwIhAOwm5dDlqxmxG5PrAwQ8A1fDtsJRfSfh4/YaAKFb
+        wn3G
+        -----END CERTIFICATE-----
+    ";
+
+    fn secret_cluster() -> FakeKube {
+        let mut kube = fake_cluster();
+        let mut db = secret("db", "Opaque", &[("password", "hunter2"), ("username", "admin")]);
+        db.data.insert("keystore".to_string(), encode_base64(&[0xfe, 0xed, 0x00, 0xff]));
+        db.data.insert("token".to_string(), "not base64!".to_string());
+        kube.secrets.insert(
+            "default".to_string(),
+            vec![
+                db,
+                secret(
+                    "web-tls",
+                    "kubernetes.io/tls",
+                    &[("tls.crt", CERTIFICATE), ("tls.key", "")],
+                ),
+            ],
+        );
+        kube
+    }
+
+    /// Opens the secret list and the details of the secret `down` rows below
+    /// the first.
+    fn open_secret(app: &mut App, down: usize) {
+        select_menu(app, "Secrets");
+        press(app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::SecretList);
+        for _ in 0..down {
+            press(app, &[KeyCode::Down]);
+        }
+        press(app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::SecretDetail);
+    }
+
+    #[test]
+    fn secrets_are_listed_with_their_type() {
+        let (mut app, _) = app_with(secret_cluster());
+
+        select_menu(&mut app, "Secrets");
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::SecretList);
+        assert!(render(&mut app).iter().any(|line| line.contains("kubernetes.io/tls")));
+    }
+
+    #[test]
+    fn secret_values_are_masked_until_revealed() {
+        let (mut app, _) = app_with(secret_cluster());
+        open_secret(&mut app, 0);
+
+        let screen = render(&mut app).join("\n");
+        assert!(screen.contains("password  •••••••• (7 bytes)"));
+        assert!(!screen.contains("hunter2"));
+        press(&mut app, &[KeyCode::Char('v')]);
+        assert_eq!(app.secret_view.as_ref().unwrap().selected_key(), Some("keystore"));
+        assert!(render(&mut app).iter().any(|line| line.contains("<4 bytes of binary data>")));
+        press(&mut app, &[KeyCode::Down, KeyCode::Char('v')]);
+        assert!(render(&mut app).iter().any(|line| line.contains("password  hunter2")));
+        assert!(!render(&mut app).iter().any(|line| line.contains("admin")));
+    }
+
+    #[test]
+    fn secret_values_are_copied_decoded() {
+        let (mut app, _) = app_with(secret_cluster());
+        open_secret(&mut app, 0);
+
+        let path = std::env::temp_dir().join(format!("kubetui-clipboard-{}", std::process::id()));
+        app.clipboard = Some(format!("tee {}", path.display()));
+        // Up from the first key wraps around to the last, like the lists.
+        press(&mut app, &[KeyCode::Up, KeyCode::Char('c')]);
+        assert_eq!(app.secret_view.as_ref().unwrap().selected_key(), Some("username"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "admin");
+        assert_eq!(app.status_message.as_deref(), Some("Copied username to the clipboard"));
+
+        // Binary values are copied byte for byte, not as their placeholder.
+        press(&mut app, &[KeyCode::Down, KeyCode::Char('c')]);
+        assert_eq!(app.secret_view.as_ref().unwrap().selected_key(), Some("keystore"));
+        assert_eq!(std::fs::read(&path).unwrap(), [0xfe, 0xed, 0x00, 0xff]);
+        std::fs::remove_file(&path).unwrap();
+
+        press(&mut app, &[KeyCode::Up, KeyCode::Up, KeyCode::Char('c')]);
+        assert_eq!(app.secret_view.as_ref().unwrap().selected_key(), Some("token"));
+        assert_eq!(app.status_message.as_deref(), Some("Cannot copy token: not valid base64"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn tls_secrets_show_their_certificate() {
+        let (mut app, _) = app_with(secret_cluster());
+        open_secret(&mut app, 1);
+
+        let cert = app.secret_view.as_ref().unwrap().certificate.clone().unwrap();
+        assert_eq!(cert.subject, "O=Example, CN=web.example.com");
+        assert_eq!(cert.issuer, cert.subject);
+        assert!(!cert.is_expired());
+        assert!(render(&mut app).iter().any(|line| line.contains("Expires  in 36")));
+    }
+
+    #[test]
+    fn lists_filter_fuzzily_and_pods_by_label() {
+        let mut kube = fake_cluster();
+        kube.pods.insert(
+            "payments".to_string(),
+            vec![
+                labelled_pod("web-2", &[("app", "web")]),
+                labelled_pod("api-db-0", &[("app", "api"), ("tier", "db")]),
+                labelled_pod("api-7f9c", &[("app", "api"), ("tier", "web")]),
+            ],
+        );
+        let (mut app, _) = app_with(kube);
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        let keys: Vec<KeyCode> = "/pay".chars().map(KeyCode::Char).collect();
+        press(&mut app, &keys);
+        assert!(app.is_text_input());
+        assert_eq!(app.highlighted_name().as_deref(), Some("payments"));
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(render(&mut app)
+            .iter()
+            .any(|line| line.contains("Select Namespace /pay [1/2]")));
+        press(&mut app, &[KeyCode::Enter]);
+        assert_eq!(app.current_namespace(), "payments");
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter, KeyCode::Down]);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-db-0"));
+        let keys: Vec<KeyCode> = "/adb".chars().map(KeyCode::Char).collect();
+        press(&mut app, &keys);
+        assert_eq!(app.visible_indices(), vec![1]);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-db-0"));
+        assert_eq!(
+            filter::highlight("api-db-0", app.fuzzy_query())
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect::<Vec<_>>(),
+            ["a", "pi-", "db", "-0"]
+        );
+
+        press(&mut app, &[KeyCode::Backspace; 3]);
+        let keys: Vec<KeyCode> = "app=api,tier!=db".chars().map(KeyCode::Char).collect();
+        press(&mut app, &keys);
+        assert_eq!(app.visible_indices(), vec![0]);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-7f9c"));
+
+        press(&mut app, &[KeyCode::Enter, KeyCode::Down]);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-7f9c"));
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.state == AppState::ExecPodSelection);
+        assert_eq!(app.visible_indices().len(), 3);
+        assert_eq!(app.selected_pod_name().as_deref(), Some("api-7f9c"));
+    }
+
+    #[test]
+    fn production_bulk_deletes_require_typing_the_context() {
+        let (mut app, kube) = app_with(fake_cluster());
+        app.switch_context("prod").unwrap();
+
+        app.list_state.select(Some(2));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('a'), KeyCode::Char('x')]);
+        assert!(render(&mut app).iter().any(|line| line.contains("Type prod to confirm")));
+
+        press(&mut app, &[KeyCode::Char('2'), KeyCode::Enter]);
+        assert!(app.state == AppState::ConfirmDelete);
+        assert_eq!(app.status_message.as_deref(), Some("Type prod to confirm"));
+
+        press(&mut app, &[KeyCode::Backspace]);
+        for c in "prod".chars() {
+            press(&mut app, &[KeyCode::Char(c)]);
+        }
+        press(&mut app, &[KeyCode::Enter]);
+        assert!(app.state == AppState::BulkSummary);
+        assert!(kube.calls().contains(&"run delete pod api-1 -n default".to_string()));
+        assert!(kube.calls().contains(&"run delete pod web-2 -n default".to_string()));
+    }
+
+    #[test]
+    fn exec_returns_to_pod_list_with_exit_code() {
+        let (mut app, kube) = app_with(FakeKube {
+            exec_exit_code: 130,
+            ..fake_cluster()
+        });
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter, KeyCode::Enter]);
+        assert!(app.state == AppState::ShellSelection);
+
+        press(&mut app, &[KeyCode::Enter]);
+        let session = app.interactive.take().expect("exec should be requested");
+        app.run_interactive(session);
+
+        assert!(app.state == AppState::ExecPodSelection);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Exec session in api-1 exited with code 130")
+        );
+        assert!(kube.calls().contains(&"exec default api-1 api bash".to_string()));
+        assert!(render(&mut app)
+            .iter()
+            .any(|line| line.contains("exited with code 130")));
+    }
+
+    #[test]
+    fn exec_picks_container_and_falls_back_to_sh() {
+        let (mut app, kube) = app_with(FakeKube {
+            missing_commands: vec!["bash".to_string()],
+            ..fake_cluster()
+        });
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        assert!(app.state == AppState::ExecContainerSelection);
+        assert!(render(&mut app)
+            .iter()
+            .any(|line| line.contains("debugger (ephemeral)")));
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter, KeyCode::Enter]);
+        let session = app.interactive.take().expect("exec should be requested");
+        app.run_interactive(session);
+
+        assert!(kube.calls().contains(&"exec default web-2 debugger sh".to_string()));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Exec session in web-2 exited with code 0 (bash not found, used sh)")
+        );
+    }
+
+    #[test]
+    fn exec_runs_a_custom_command() {
+        let (mut app, kube) = app_with(fake_cluster());
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Enter, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+        assert!(app.state == AppState::ExecCommandInput);
+
+        let keys: Vec<KeyCode> = "cat /etc/hosts".chars().map(KeyCode::Char).collect();
+        press(&mut app, &keys);
+        press(&mut app, &[KeyCode::Enter]);
+        let session = app.interactive.take().expect("exec should be requested");
+        app.run_interactive(session);
+
+        assert!(kube
+            .calls()
+            .contains(&"exec default api-1 api cat /etc/hosts".to_string()));
+    }
+
+    #[test]
+    fn cancelled_requests_are_ignored() {
+        let (mut app, _) = app_with(fake_cluster());
+
+        app.list_state.select(Some(1));
+        app.load_namespaces();
+        handle_main_menu(&mut app, KeyCode::Esc);
+        settle(&mut app);
+        thread::sleep(Duration::from_millis(20));
+        app.poll_jobs();
+
+        assert!(app.state == AppState::MainMenu);
+        assert!(app.namespaces.is_empty());
+    }
+
+    /// Polls the log stream until kubectl has exited.
+    fn drain_logs(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !app.logs.as_ref().unwrap().finished {
+            assert!(Instant::now() < deadline, "log stream did not end");
+            app.logs.as_mut().unwrap().poll();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn logs_stream_through_the_backend_and_restart_with_options() {
+        let (mut app, kube) = app_with(FakeKube {
+            scripts: HashMap::from([
+                (
+                    "logs -f -n default api-1 -c api".to_string(),
+                    "echo starting; echo 'panic: boom' >&2; exit 1".to_string(),
+                ),
+                (
+                    "logs -f -n default api-1 -c api --timestamps".to_string(),
+                    "echo '2024-01-31T12:00:00Z starting'".to_string(),
+                ),
+            ]),
+            ..fake_cluster()
+        });
+
+        app.list_state.select(Some(2));
+        press(&mut app, &[KeyCode::Enter, KeyCode::Char('l')]);
+        assert!(app.state == AppState::PodLogs);
+        drain_logs(&mut app);
+        let logs = app.logs.as_ref().unwrap();
+        assert_eq!(logs.lines.len(), 2);
+        assert!(logs.lines.contains(&"panic: boom".to_string()));
+        assert!(logs.title().ends_with("(following) (ended)"));
+
+        press(&mut app, &[KeyCode::Char('t')]);
+        drain_logs(&mut app);
+        assert_eq!(app.logs.as_ref().unwrap().lines, ["2024-01-31T12:00:00Z starting"]);
+        assert!(kube
+            .calls()
+            .contains(&"spawn logs -f -n default api-1 -c api --timestamps".to_string()));
+    }
+
+    #[test]
+    fn snapshot_main_menu() {
+        let (mut app, _) = app_with(fake_cluster());
+        assert_eq!(
+            render(&mut app),
+            [
+                "",
+                "  Context: dev | Namespace: default",
+                "",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " ┌Commands─────────────┐┌Welcome──────────────────────────────────────────────┐",
+                " │▶Choose Context      ││                                                     │",
+                " │ Choose Namespace    ││                                                     │",
+                " │ Pods                ││                                                     │",
+                " │ Copy Pod            ││                                                     │",
+                " │ Debug Pod           ││                                                     │",
+                " │ Resources           ││                                                     │",
+                " └─────────────────────┘└─────────────────────────────────────────────────────┘",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " [↑/↓ or j/k] Navigate  [Enter/Right] Select  [q] Quit",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_namespace_list() {
+        let (mut app, _) = app_with(fake_cluster());
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        assert_eq!(
+            render(&mut app),
+            [
+                "",
+                "  Context: dev | Namespace: default",
+                "",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " ┌Commands─────────────┐┌Select Namespace─────────────────────────────────────┐",
+                " │ Choose Context      ││▶default                                             │",
+                " │▶Choose Namespace    ││ payments                                            │",
+                " │ Pods                ││                                                     │",
+                " │ Copy Pod            ││                                                     │",
+                " │ Debug Pod           ││                                                     │",
+                " │ Resources           ││                                                     │",
+                " └─────────────────────┘└─────────────────────────────────────────────────────┘",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " [↑/↓ or j/k] Navigate  [Enter] Select  [/] Filter  [d] Describe  [y] YAML  [Es",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_context_list() {
+        let (mut app, _) = app_with(fake_cluster());
+        press(&mut app, &[KeyCode::Enter, KeyCode::Down]);
+        assert_eq!(
+            render(&mut app),
+            [
+                "",
+                "  Context: dev | Namespace: default",
+                "",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " ┌Commands─────────────┐┌Select Context───────────────────────────────────────┐",
+                " │▶Choose Context      ││ dev                                                 │",
+                " │ Choose Namespace    ││▶prod                                                │",
+                " │ Pods                ││                                                     │",
+                " │ Copy Pod            ││                                                     │",
+                " │ Debug Pod           ││                                                     │",
+                " │ Resources           ││                                                     │",
+                " └─────────────────────┘└─────────────────────────────────────────────────────┘",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " [↑/↓ or j/k] Navigate  [Enter/Right] Select  [/] Filter  [Esc] Back  [q] Quit",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_copy_pod_form() {
+        let (mut app, _) = app_with(fake_cluster());
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+        press(&mut app, &[KeyCode::Enter, KeyCode::Enter, KeyCode::Down]);
+        assert_eq!(
+            render(&mut app),
+            [
+                "",
+                "  Context: dev | Namespace: default",
+                "",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " ┌Commands─────────────┐┌Copy Pod api-1───────────────────────────────────────┐",
+                " │ Choose Context      ││ New pod name:      api-1-debug                      │",
+                " │ Choose Namespace    ││▶Target container:  ◀ api ▶                          │",
+                " │ Pods                ││ Image:             (keep)                           │",
+                " │▶Copy Pod            ││ Set image:                                          │",
+                " │ Debug Pod           ││ Share processes:   [x]                              │",
+                " │ Resources           ││ Same node:         [ ]                              │",
+                " └─────────────────────┘└─────────────────────────────────────────────────────┘",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " [↑/↓/Tab] Field  [←/→/Space] Change  [Enter] Copy  [Esc] Back",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_output() {
+        let (mut app, _) = app_with(FakeKube {
+            outputs: HashMap::from([(
+                "debug -n default api-1 --copy-to=api-1-debug --container=api \
+                 --share-processes=true"
+                    .to_string(),
+                "pod/api-1-debug created".to_string(),
+            )]),
+            ..fake_cluster()
+        });
+        press(&mut app, &[KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+        press(&mut app, &[KeyCode::Enter, KeyCode::Enter]);
+        press(&mut app, &[KeyCode::Up, KeyCode::Char(' '), KeyCode::Enter]);
+        assert_eq!(
+            render(&mut app),
+            [
+                "",
+                "  Context: dev | Namespace: default",
+                "",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " ┌Commands─────────────┐┌Output───────────────────────────────────────────────┐",
+                " │ Choose Context      ││pod/api-1-debug created                              │",
+                " │ Choose Namespace    ││                                                     │",
+                " │ Pods                ││                                                     │",
+                " │▶Copy Pod            ││                                                     │",
+                " │ Debug Pod           ││                                                     │",
+                " │ Resources           ││                                                     │",
+                " └─────────────────────┘└─────────────────────────────────────────────────────┘",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " Press any key to return to main menu, or [q] Quit",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_message() {
+        let (mut app, _) = app_with(FakeKube {
+            namespaces: None,
+            ..fake_cluster()
+        });
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+        assert_eq!(
+            render(&mut app),
+            [
+                "",
+                "  Context: dev | Namespace: default",
+                "",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " ┌Commands─────────────┐┌Message──────────────────────────────────────────────┐",
+                " │ Choose Context      ││Error loading namespaces: Failed to get namespaces:  │",
+                " │▶Choose Namespace    ││forbidden                                            │",
+                " │ Pods                ││                                                     │",
+                " │ Copy Pod            ││                                                     │",
+                " │ Debug Pod           ││                                                     │",
+                " │ Resources           ││                                                     │",
+                " └─────────────────────┘└─────────────────────────────────────────────────────┘",
+                " ──────────────────────────────────────────────────────────────────────────────",
+                " Press any key to return to main menu, or [q] Quit",
+                "",
+                "",
+            ]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+const DEFAULT_INTERVAL_SECS: u64 = 5;
+
+/// How a row differs from the previous load of its list.
+///
+/// Only rows still in the list can be highlighted, so removals are not a
+/// change: a deleted pod shows as `Terminating` through its own status for
+/// as long as kubectl still lists it, and then simply drops out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowChange {
+    Added,
+    Changed,
+}
+
+/// Periodic refresh of the pod and namespace lists while they are on screen.
+pub struct ListWatch {
+    /// Time between refreshes, or None if watching is turned off.
+    pub interval: Option<Duration>,
+    pub last_refresh: Option<Instant>,
+    /// Rows added or changed by the latest refresh, by name.
+    pub changes: HashMap<String, RowChange>,
+}
+
+impl Default for ListWatch {
+    fn default() -> Self {
+        ListWatch {
+            interval: watch_interval(),
+            last_refresh: None,
+            changes: HashMap::new(),
+        }
+    }
+}
+
+impl ListWatch {
+    /// Whether the list is due for a refresh.
+    pub fn due(&self, now: Instant) -> bool {
+        match (self.interval, self.last_refresh) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(interval), Some(last)) => now.duration_since(last) >= interval,
+        }
+    }
+
+    /// Records a freshly loaded list. On a reload, rows that differ from
+    /// `old` are remembered for highlighting.
+    pub fn record<T: PartialEq>(
+        &mut self,
+        old: &[T],
+        new: &[T],
+        name: impl Fn(&T) -> &str,
+        reload: bool,
+    ) {
+        self.last_refresh = Some(Instant::now());
+        self.changes = if reload {
+            diff(old, new, name)
+        } else {
+            HashMap::new()
+        };
+    }
+}
+
+/// Rows of `new` that are missing from or differ from `old`, matched by name.
+pub fn diff<T: PartialEq>(
+    old: &[T],
+    new: &[T],
+    name: impl Fn(&T) -> &str,
+) -> HashMap<String, RowChange> {
+    new.iter()
+        .filter_map(|item| {
+            let change = match old.iter().find(|o| name(o) == name(item)) {
+                None => RowChange::Added,
+                Some(previous) if previous != item => RowChange::Changed,
+                Some(_) => return None,
+            };
+            Some((name(item).to_string(), change))
+        })
+        .collect()
+}
+
+/// Reads KUBETUI_WATCH_INTERVAL, in seconds. Zero turns watching off.
+pub fn watch_interval() -> Option<Duration> {
+    parse_interval(std::env::var("KUBETUI_WATCH_INTERVAL").ok().as_deref())
+}
+
+/// The refresh interval for a KUBETUI_WATCH_INTERVAL value, falling back to
+/// the default when it is unset or not a number.
+fn parse_interval(value: Option<&str>) -> Option<Duration> {
+    let seconds = value
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_INTERVAL_SECS);
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Row {
+        name: &'static str,
+        status: &'static str,
+    }
+
+    fn row(name: &'static str, status: &'static str) -> Row {
+        Row { name, status }
+    }
+
+    #[test]
+    fn diff_reports_added_and_changed_rows_only() {
+        let old = [row("api-0", "Running"), row("api-1", "Running"), row("db-0", "Running")];
+        let new = [row("api-1", "Terminating"), row("api-2", "Pending"), row("db-0", "Running")];
+
+        let changes = diff(&old, &new, |r| r.name);
+        assert_eq!(
+            changes,
+            HashMap::from([
+                ("api-1".to_string(), RowChange::Changed),
+                ("api-2".to_string(), RowChange::Added),
+            ])
+        );
+        // api-0 disappeared, which leaves nothing to highlight.
+        assert!(!changes.contains_key("api-0"));
+        assert!(diff(&new, &new, |r| r.name).is_empty());
+    }
+
+    #[test]
+    fn only_reloads_remember_changes() {
+        let mut watch = ListWatch {
+            interval: Some(Duration::from_secs(5)),
+            last_refresh: None,
+            changes: HashMap::new(),
+        };
+        let old = [row("api-0", "Running")];
+        let new = [row("api-0", "Running"), row("api-1", "Pending")];
+
+        watch.record(&[], &old, |r| r.name, false);
+        assert!(watch.changes.is_empty());
+        assert!(watch.last_refresh.is_some());
+
+        watch.record(&old, &new, |r| r.name, true);
+        assert_eq!(watch.changes.get("api-1"), Some(&RowChange::Added));
+
+        watch.record(&new, &old, |r| r.name, false);
+        assert!(watch.changes.is_empty());
+    }
+
+    #[test]
+    fn refreshes_are_due_once_the_interval_passed() {
+        let start = Instant::now();
+        let mut watch = ListWatch {
+            interval: Some(Duration::from_secs(5)),
+            last_refresh: None,
+            changes: HashMap::new(),
+        };
+        assert!(watch.due(start));
+
+        watch.last_refresh = Some(start);
+        assert!(!watch.due(start + Duration::from_secs(4)));
+        assert!(watch.due(start + Duration::from_secs(5)));
+
+        watch.interval = None;
+        assert!(!watch.due(start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn parses_the_interval_setting() {
+        assert_eq!(parse_interval(None), Some(Duration::from_secs(5)));
+        assert_eq!(parse_interval(Some(" 30 ")), Some(Duration::from_secs(30)));
+        assert_eq!(parse_interval(Some("0")), None);
+        assert_eq!(parse_interval(Some("soon")), Some(Duration::from_secs(5)));
+        assert_eq!(parse_interval(Some("-1")), Some(Duration::from_secs(5)));
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+pub fn spinner_frame(tick: usize) -> &'static str {
+    SPINNER_FRAMES[tick % SPINNER_FRAMES.len()]
+}
+
+struct PendingTask {
+    id: u64,
+    label: String,
+    /// Background tasks, such as periodic refreshes, survive `cancel_all`.
+    background: bool,
+}
+
+/// Runs blocking tasks (typically kubectl invocations) on background threads
+/// and hands their results back to the event loop through a channel.
+///
+/// Cancelling a task only forgets about it: the thread runs to completion and
+/// its result is discarded when it arrives.
+pub struct Worker<T> {
+    sender: Sender<(u64, T)>,
+    receiver: Receiver<(u64, T)>,
+    pending: Vec<PendingTask>,
+    next_id: u64,
+}
+
+impl<T: Send + 'static> Default for Worker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Send + 'static> Worker<T> {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Worker {
+            sender,
+            receiver,
+            pending: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Starts `task` on a new thread. A pending task with the same label is
+    /// superseded, so only the most recent request of each kind is delivered.
+    pub fn spawn<F>(&mut self, label: &str, task: F)
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        self.start(label, false, task);
+    }
+
+    /// Like `spawn`, for work the user did not ask for and so cannot cancel.
+    pub fn spawn_background<F>(&mut self, label: &str, task: F)
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        self.start(label, true, task);
+    }
+
+    fn start<F>(&mut self, label: &str, background: bool, task: F)
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        self.pending.retain(|p| p.label != label);
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.push(PendingTask {
+            id,
+            label: label.to_string(),
+            background,
+        });
+
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let _ = sender.send((id, task()));
+        });
+    }
+
+    /// Returns the results of every task that finished since the last call.
+    pub fn poll(&mut self) -> Vec<T> {
+        let mut results = Vec::new();
+        while let Ok((id, result)) = self.receiver.try_recv() {
+            if let Some(index) = self.pending.iter().position(|p| p.id == id) {
+                self.pending.remove(index);
+                results.push(result);
+            }
+        }
+        results
+    }
+
+    /// Forgets every pending task except background ones. Returns whether
+    /// anything was cancelled.
+    pub fn cancel_all(&mut self) -> bool {
+        let before = self.pending.len();
+        self.pending.retain(|p| p.background);
+        self.pending.len() < before
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Label of the most recently started task that is still pending.
+    pub fn current_label(&self) -> Option<&str> {
+        self.pending.last().map(|p| p.label.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    /// Polls until nothing is pending, collecting every delivered result.
+    fn finish(worker: &mut Worker<u32>) -> Vec<u32> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut results = Vec::new();
+        while worker.is_busy() {
+            assert!(Instant::now() < deadline, "tasks did not finish");
+            results.extend(worker.poll());
+            thread::sleep(Duration::from_millis(1));
+        }
+        results
+    }
+
+    #[test]
+    fn newer_task_with_the_same_label_supersedes_the_pending_one() {
+        let mut worker = Worker::new();
+        let (release, gate) = mpsc::channel::<()>();
+        worker.spawn("Loading pods", move || {
+            let _ = gate.recv();
+            1
+        });
+        worker.spawn("Loading pods", || 2);
+        worker.spawn("Loading events", || 3);
+        assert_eq!(worker.current_label(), Some("Loading events"));
+
+        let mut results = finish(&mut worker);
+        release.send(()).unwrap();
+        thread::sleep(Duration::from_millis(20));
+        results.extend(worker.poll());
+        results.sort();
+        assert_eq!(results, [2, 3]);
+    }
+
+    #[test]
+    fn cancelled_tasks_are_forgotten() {
+        let mut worker = Worker::new();
+        assert!(!worker.cancel_all());
+        worker.spawn("Describing", || 1);
+        assert!(worker.is_busy());
+        assert!(worker.cancel_all());
+        assert!(!worker.is_busy());
+        assert_eq!(worker.current_label(), None);
+
+        thread::sleep(Duration::from_millis(20));
+        assert!(worker.poll().is_empty());
+    }
+
+    #[test]
+    fn background_tasks_survive_cancel_all() {
+        let mut worker = Worker::new();
+        worker.spawn_background("Refreshing pods", || 1);
+        assert!(!worker.cancel_all());
+        worker.spawn("Describing", || 2);
+        assert!(worker.cancel_all());
+        assert_eq!(worker.current_label(), Some("Refreshing pods"));
+        assert_eq!(finish(&mut worker), [1]);
+    }
+
+    #[test]
+    fn spinner_cycles_through_its_frames() {
+        assert_eq!(spinner_frame(0), spinner_frame(SPINNER_FRAMES.len()));
+        assert_ne!(spinner_frame(0), spinner_frame(1));
+    }
+}
@@ -0,0 +1,464 @@
+use std::error::Error;
+
+use serde_json::Value;
+
+use crate::pods::{format_age, parse_timestamp};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deployment {
+    pub name: String,
+    pub desired: u64,
+    pub ready: u64,
+    pub updated: u64,
+    pub available: u64,
+    /// Creation time as seconds since the Unix epoch.
+    pub created: Option<i64>,
+}
+
+impl Deployment {
+    fn from_json(item: &Value) -> Option<Deployment> {
+        let status = &item["status"];
+        let count = |value: &Value| value.as_u64().unwrap_or(0);
+        Some(Deployment {
+            name: item["metadata"]["name"].as_str()?.to_string(),
+            desired: count(&item["spec"]["replicas"]),
+            ready: count(&status["readyReplicas"]),
+            updated: count(&status["updatedReplicas"]),
+            available: count(&status["availableReplicas"]),
+            created: item["metadata"]["creationTimestamp"]
+                .as_str()
+                .and_then(parse_timestamp),
+        })
+    }
+
+    pub fn ready_display(&self) -> String {
+        format!("{}/{}", self.ready, self.desired)
+    }
+
+    pub fn age_display(&self) -> String {
+        age_display(self.created)
+    }
+
+    /// Whether every desired replica is updated and available.
+    pub fn is_available(&self) -> bool {
+        self.updated >= self.desired && self.available >= self.desired
+    }
+}
+
+fn age_display(created: Option<i64>) -> String {
+    created.map_or_else(|| "<unknown>".to_string(), format_age)
+}
+
+pub(crate) fn parse_items<T>(
+    json: &str,
+    parse: impl Fn(&Value) -> Option<T>,
+) -> Result<Vec<T>, Box<dyn Error>> {
+    let value: Value = serde_json::from_str(json)?;
+    let items = value["items"]
+        .as_array()
+        .ok_or("Unexpected list format")?;
+    Ok(items.iter().filter_map(parse).collect())
+}
+
+/// Parses the output of `kubectl get deployments -o json`.
+pub fn parse_deployments(json: &str) -> Result<Vec<Deployment>, Box<dyn Error>> {
+    parse_items(json, Deployment::from_json)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkloadKind {
+    StatefulSet,
+    DaemonSet,
+    Job,
+    CronJob,
+}
+
+impl WorkloadKind {
+    pub const ALL: [WorkloadKind; 4] = [
+        WorkloadKind::StatefulSet,
+        WorkloadKind::DaemonSet,
+        WorkloadKind::Job,
+        WorkloadKind::CronJob,
+    ];
+
+    /// The name kubectl accepts for `get` and in `kind/name` references.
+    pub fn resource(self) -> &'static str {
+        match self {
+            WorkloadKind::StatefulSet => "statefulset",
+            WorkloadKind::DaemonSet => "daemonset",
+            WorkloadKind::Job => "job",
+            WorkloadKind::CronJob => "cronjob",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            WorkloadKind::StatefulSet => "StatefulSets",
+            WorkloadKind::DaemonSet => "DaemonSets",
+            WorkloadKind::Job => "Jobs",
+            WorkloadKind::CronJob => "CronJobs",
+        }
+    }
+
+    /// Column headers after NAME, matching `Workload::columns`.
+    pub fn headers(self) -> &'static [&'static str] {
+        match self {
+            WorkloadKind::StatefulSet => &["READY", "AGE"],
+            WorkloadKind::DaemonSet => &["DESIRED", "CURRENT", "READY", "UP-TO-DATE", "AGE"],
+            WorkloadKind::Job => &["COMPLETIONS", "STATUS", "AGE"],
+            WorkloadKind::CronJob => &["SCHEDULE", "SUSPEND", "ACTIVE", "LAST SCHEDULE", "AGE"],
+        }
+    }
+
+    /// Whether `kubectl rollout restart` applies.
+    pub fn restartable(self) -> bool {
+        matches!(self, WorkloadKind::StatefulSet | WorkloadKind::DaemonSet)
+    }
+}
+
+/// A row of one of the StatefulSet, DaemonSet, Job or CronJob lists.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Workload {
+    pub name: String,
+    /// Kind-specific column values, as listed by `WorkloadKind::headers`.
+    pub columns: Vec<String>,
+    /// All replicas ready, or a job that completed.
+    pub healthy: bool,
+    /// A job that completed or failed.
+    pub finished: bool,
+    /// A suspended cronjob.
+    pub suspended: bool,
+}
+
+impl Workload {
+    fn from_json(kind: WorkloadKind, item: &Value) -> Option<Workload> {
+        let metadata = &item["metadata"];
+        let spec = &item["spec"];
+        let status = &item["status"];
+        let count = |value: &Value| value.as_u64().unwrap_or(0);
+        let age = age_display(metadata["creationTimestamp"].as_str().and_then(parse_timestamp));
+
+        let mut workload = Workload {
+            name: metadata["name"].as_str()?.to_string(),
+            columns: Vec::new(),
+            healthy: false,
+            finished: false,
+            suspended: false,
+        };
+        match kind {
+            WorkloadKind::StatefulSet => {
+                let desired = count(&spec["replicas"]);
+                let ready = count(&status["readyReplicas"]);
+                workload.healthy = ready >= desired;
+                workload.columns = vec![format!("{}/{}", ready, desired), age];
+            }
+            WorkloadKind::DaemonSet => {
+                let desired = count(&status["desiredNumberScheduled"]);
+                let ready = count(&status["numberReady"]);
+                workload.healthy = ready >= desired;
+                workload.columns = vec![
+                    desired.to_string(),
+                    count(&status["currentNumberScheduled"]).to_string(),
+                    ready.to_string(),
+                    count(&status["updatedNumberScheduled"]).to_string(),
+                    age,
+                ];
+            }
+            WorkloadKind::Job => {
+                let completions = spec["completions"].as_u64().unwrap_or(1);
+                let succeeded = count(&status["succeeded"]);
+                let job_status = job_status(spec, status);
+                workload.healthy = job_status == "Complete";
+                workload.finished = matches!(job_status, "Complete" | "Failed");
+                workload.columns = vec![
+                    format!("{}/{}", succeeded, completions),
+                    job_status.to_string(),
+                    age,
+                ];
+            }
+            WorkloadKind::CronJob => {
+                workload.suspended = spec["suspend"].as_bool().unwrap_or(false);
+                workload.healthy = !workload.suspended;
+                let last_schedule = status["lastScheduleTime"].as_str().and_then(parse_timestamp);
+                workload.columns = vec![
+                    spec["schedule"].as_str().unwrap_or("").to_string(),
+                    if workload.suspended { "True" } else { "False" }.to_string(),
+                    status["active"].as_array().map_or(0, |a| a.len()).to_string(),
+                    last_schedule.map_or_else(|| "<none>".to_string(), format_age),
+                    age,
+                ];
+            }
+        }
+        Some(workload)
+    }
+}
+
+/// A job's state from its conditions, as `kubectl get jobs` shows it.
+fn job_status(spec: &Value, status: &Value) -> &'static str {
+    let conditions = status["conditions"].as_array().map(|c| c.as_slice()).unwrap_or_default();
+    let holds = |kind: &str| {
+        conditions
+            .iter()
+            .any(|c| c["type"].as_str() == Some(kind) && c["status"].as_str() == Some("True"))
+    };
+    if holds("Complete") {
+        "Complete"
+    } else if holds("Failed") {
+        "Failed"
+    } else if spec["suspend"].as_bool().unwrap_or(false) {
+        "Suspended"
+    } else {
+        "Running"
+    }
+}
+
+/// Parses the output of `kubectl get <kind> -o json`.
+pub fn parse_workloads(kind: WorkloadKind, json: &str) -> Result<Vec<Workload>, Box<dyn Error>> {
+    parse_items(json, |item| Workload::from_json(kind, item))
+}
+
+/// Name for a job created by hand from `cronjob`, like the one
+/// `kubectl create job --from` suggests, kept within the 63 character limit.
+pub fn manual_job_name(cronjob: &str, timestamp: i64) -> String {
+    let suffix = format!("-manual-{}", timestamp);
+    let keep = 63usize.saturating_sub(suffix.len()).min(cronjob.len());
+    format!("{}{}", cronjob[..keep].trim_end_matches('-'), suffix)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Revision {
+    pub number: u64,
+    pub change_cause: String,
+}
+
+impl Revision {
+    pub fn display(&self) -> String {
+        if self.change_cause.is_empty() || self.change_cause == "<none>" {
+            format!("Revision {}", self.number)
+        } else {
+            format!("Revision {}: {}", self.number, self.change_cause)
+        }
+    }
+}
+
+/// Parses `kubectl rollout history`, newest revision first.
+pub fn parse_rollout_history(output: &str) -> Vec<Revision> {
+    let mut revisions: Vec<Revision> = output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (number, cause) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            Some(Revision {
+                number: number.parse().ok()?,
+                change_cause: cause.trim().to_string(),
+            })
+        })
+        .collect();
+    revisions.sort_by_key(|r| std::cmp::Reverse(r.number));
+    revisions
+}
+
+/// Progress of a deployment rollout, refreshed by polling
+/// `kubectl rollout status --watch=false`.
+pub struct RolloutWatch {
+    pub deployment: String,
+    /// Distinct status messages in the order they were seen.
+    pub messages: Vec<String>,
+    pub progress: Option<Deployment>,
+    pub done: bool,
+    pub polling: bool,
+    pub last_poll: Option<usize>,
+}
+
+impl RolloutWatch {
+    pub fn new(deployment: &str) -> Self {
+        RolloutWatch {
+            deployment: deployment.to_string(),
+            messages: Vec::new(),
+            progress: None,
+            done: false,
+            polling: false,
+            last_poll: None,
+        }
+    }
+
+    pub fn record(&mut self, status: &str) {
+        for line in status.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            if self.messages.last().map(|m| m.as_str()) != Some(line) {
+                self.messages.push(line.to_string());
+            }
+            if line.contains("successfully rolled out") {
+                self.done = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPLOYMENTS: &str = r#"{"apiVersion": "v1", "kind": "List", "items": [
+        {
+            "metadata": {"name": "api", "namespace": "default",
+                         "creationTimestamp": "2024-01-31T12:00:00Z"},
+            "spec": {"replicas": 3},
+            "status": {"replicas": 3, "readyReplicas": 2, "updatedReplicas": 3,
+                       "availableReplicas": 2, "observedGeneration": 4}
+        },
+        {
+            "metadata": {"name": "idle", "namespace": "default"},
+            "spec": {"replicas": 0},
+            "status": {"observedGeneration": 1}
+        },
+        {"metadata": {}}
+    ]}"#;
+
+    #[test]
+    fn parses_deployments_and_their_availability() {
+        let deployments = parse_deployments(DEPLOYMENTS).unwrap();
+        assert_eq!(deployments.len(), 2);
+
+        let api = &deployments[0];
+        assert_eq!(api.name, "api");
+        assert_eq!((api.desired, api.ready, api.updated, api.available), (3, 2, 3, 2));
+        assert_eq!(api.ready_display(), "2/3");
+        assert_eq!(api.created, Some(1_706_702_400));
+        assert!(!api.is_available());
+
+        let idle = &deployments[1];
+        assert_eq!(idle.ready_display(), "0/0");
+        assert_eq!(idle.age_display(), "<unknown>");
+        assert!(idle.is_available());
+
+        assert!(parse_deployments(r#"{"kind": "Status"}"#).is_err());
+        assert!(parse_deployments("not json").is_err());
+    }
+
+    #[test]
+    fn parses_rollout_history_newest_first() {
+        let output = "deployment.apps/api \n\
+                      REVISION  CHANGE-CAUSE\n\
+                      1         <none>\n\
+                      3         kubectl set image deployment/api api=api:2.0\n\
+                      2         <none>\n\n";
+
+        let revisions = parse_rollout_history(output);
+        let numbers: Vec<u64> = revisions.iter().map(|r| r.number).collect();
+        assert_eq!(numbers, vec![3, 2, 1]);
+        assert_eq!(
+            revisions[0].display(),
+            "Revision 3: kubectl set image deployment/api api=api:2.0"
+        );
+        assert_eq!(revisions[1].display(), "Revision 2");
+        assert!(parse_rollout_history("error: no rollout history found").is_empty());
+    }
+
+    #[test]
+    fn rollout_watch_keeps_distinct_messages_until_done() {
+        let mut watch = RolloutWatch::new("api");
+        watch.record("Waiting for deployment \"api\" rollout to finish: 1 of 3 updated...\n");
+        watch.record("Waiting for deployment \"api\" rollout to finish: 1 of 3 updated...\n");
+        assert_eq!(watch.messages.len(), 1);
+        assert!(!watch.done);
+
+        watch.record("deployment \"api\" successfully rolled out\n");
+        assert_eq!(watch.messages.len(), 2);
+        assert!(watch.done);
+    }
+
+    /// Columns without the trailing AGE, which depends on the clock.
+    fn columns(workload: &Workload) -> Vec<&str> {
+        let (_, rest) = workload.columns.split_last().unwrap();
+        rest.iter().map(|c| c.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_statefulsets_and_daemonsets() {
+        let statefulsets = r#"{"items": [
+            {
+                "metadata": {"name": "db", "creationTimestamp": "2024-01-31T12:00:00Z"},
+                "spec": {"replicas": 3, "serviceName": "db"},
+                "status": {"replicas": 3, "readyReplicas": 3, "currentReplicas": 3}
+            },
+            {"metadata": {"name": "cache"}, "spec": {"replicas": 2}, "status": {"replicas": 2}}
+        ]}"#;
+        let workloads = parse_workloads(WorkloadKind::StatefulSet, statefulsets).unwrap();
+        assert_eq!(columns(&workloads[0]), vec!["3/3"]);
+        assert!(workloads[0].healthy);
+        assert_eq!(workloads[1].columns, vec!["0/2", "<unknown>"]);
+        assert!(!workloads[1].healthy);
+
+        let daemonsets = r#"{"items": [{
+            "metadata": {"name": "fluentd", "namespace": "kube-system"},
+            "status": {"currentNumberScheduled": 3, "desiredNumberScheduled": 3,
+                       "numberAvailable": 2, "numberReady": 2, "updatedNumberScheduled": 1}
+        }]}"#;
+        let workloads = parse_workloads(WorkloadKind::DaemonSet, daemonsets).unwrap();
+        assert_eq!(columns(&workloads[0]), vec!["3", "3", "2", "1"]);
+        assert!(!workloads[0].healthy);
+        assert_eq!(workloads[0].columns.len(), WorkloadKind::DaemonSet.headers().len());
+    }
+
+    #[test]
+    fn parses_jobs_by_their_conditions() {
+        let jobs = r#"{"items": [
+            {
+                "metadata": {"name": "migrate"},
+                "spec": {"completions": 1, "parallelism": 1},
+                "status": {"succeeded": 1, "conditions": [
+                    {"type": "Complete", "status": "True"}
+                ]}
+            },
+            {
+                "metadata": {"name": "backfill"},
+                "spec": {"completions": 5},
+                "status": {"failed": 6, "succeeded": 2, "conditions": [
+                    {"type": "Failed", "status": "True", "reason": "BackoffLimitExceeded"}
+                ]}
+            },
+            {"metadata": {"name": "paused"}, "spec": {"suspend": true}, "status": {}},
+            {"metadata": {"name": "report"}, "spec": {}, "status": {"active": 1}}
+        ]}"#;
+        let workloads = parse_workloads(WorkloadKind::Job, jobs).unwrap();
+        let rows: Vec<Vec<&str>> = workloads.iter().map(columns).collect();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["1/1", "Complete"],
+                vec!["2/5", "Failed"],
+                vec!["0/1", "Suspended"],
+                vec!["0/1", "Running"],
+            ]
+        );
+        let finished: Vec<bool> = workloads.iter().map(|w| w.finished).collect();
+        assert_eq!(finished, vec![true, true, false, false]);
+        assert!(workloads[0].healthy && !workloads[1].healthy);
+    }
+
+    #[test]
+    fn parses_cronjobs() {
+        let cronjobs = r#"{"items": [
+            {
+                "metadata": {"name": "nightly"},
+                "spec": {"schedule": "0 2 * * *", "suspend": false},
+                "status": {"active": [{"kind": "Job", "name": "nightly-28461600"}]}
+            },
+            {"metadata": {"name": "weekly"}, "spec": {"schedule": "@weekly", "suspend": true}}
+        ]}"#;
+        let workloads = parse_workloads(WorkloadKind::CronJob, cronjobs).unwrap();
+        assert_eq!(columns(&workloads[0]), vec!["0 2 * * *", "False", "1", "<none>"]);
+        assert!(workloads[0].healthy && !workloads[0].suspended);
+        assert_eq!(columns(&workloads[1]), vec!["@weekly", "True", "0", "<none>"]);
+        assert!(workloads[1].suspended && !workloads[1].healthy);
+    }
+
+    #[test]
+    fn manual_job_names_fit_the_name_limit() {
+        assert_eq!(manual_job_name("nightly", 1_706_702_400), "nightly-manual-1706702400");
+        let long = "a".repeat(70);
+        let name = manual_job_name(&long, 1_706_702_400);
+        assert_eq!(name.len(), 63);
+        assert!(name.ends_with("a-manual-1706702400"));
+    }
+}